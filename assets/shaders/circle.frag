@@ -5,5 +5,5 @@ uniform vec4 i_Color;
 uniform sampler2D t_color;
 void main() {
     vec4 tex = texture(t_color, v_TexCoord);
-    o_Color = vec4(vec3(i_Color), tex.a);
+    o_Color = vec4(vec3(i_Color), tex.a * i_Color.a);
 }
\ No newline at end of file
@@ -2,36 +2,76 @@
 in vec3 v_gpos;
 out vec4 o_Color;
 uniform float u_color_scale;
-uniform float u_trans_size;
+uniform float u_wave_num;
+uniform float u_attenuation;
+uniform int u_quantity;
+uniform float u_spl_reference;
+uniform float u_gorkov_k1;
+uniform float u_gorkov_k2;
+uniform float u_time_phase;
 uniform float u_trans_num;
 uniform sampler1D u_color_map;
-uniform sampler1D u_trans_pos;
-uniform sampler1D u_trans_pos_256;
-uniform sampler1D u_trans_pos_sub;
-uniform sampler1D u_trans_phase;
+uniform sampler2D u_trans_pos;
+uniform sampler2D u_trans_phase;
+uniform sampler2D u_trans_amp;
+uniform sampler2D u_trans_dir;
+uniform sampler1D u_directivity;
 const float PI = 3.141592653589793;
-const float WAVE_LENGTH = 8.5;
-const float WAVE_NUM = 2.0*PI/WAVE_LENGTH;
+float directivity(vec3 dir, vec3 r)
+{
+  float theta = acos(clamp(dot(dir, r) / length(r), -1.0, 1.0));
+  float n = float(textureSize(u_directivity, 0));
+  return texture(u_directivity, (theta / PI * (n - 1.0) + 0.5) / n).x;
+}
+ivec2 source_texel(int idx)
+{
+  int w = textureSize(u_trans_pos, 0).x;
+  return ivec2(idx % w, idx / w);
+}
 vec4 coloring(float t)
 {
-  return texture(u_color_map, clamp(t * u_color_scale, 0.0, 0.99));
+  return texture(u_color_map, clamp(t, 0.0, 0.99));
+}
+float quantity(float re, float im)
+{
+  float amp = sqrt(re*re + im*im);
+  if (u_quantity == 1) return 0.5 + 0.5 * re * u_color_scale;
+  if (u_quantity == 2) return (atan(im, re) + PI) / (2.0*PI);
+  if (u_quantity == 3) return amp * amp * u_color_scale;
+  if (u_quantity == 4) return 20.0 * log(amp / u_spl_reference) / log(10.0) * u_color_scale;
+  return amp * u_color_scale;
 }
 void main() {
     float re = 0.0;
     float im = 0.0;
-    for(float idx = 0.0; idx < 65536.0; idx++){
-        if (idx >= u_trans_num) break;
-        vec3 t = texture(u_trans_pos, (idx+0.5) / u_trans_num).xyz;
-        vec3 t_256 = texture(u_trans_pos_256, (idx+0.5) / u_trans_num).xyz;
-        vec3 t_sub = texture(u_trans_pos_sub, (idx+0.5) / u_trans_num).xyz;
-        vec3 tr = floor(255.0 * t);
-        vec3 tr_256 = 256.0 * floor(255.0 * t_256);
-        vec3 tp = u_trans_size * (tr + tr_256 + t_sub);
-        float p = 2.0*PI*texture(u_trans_phase, (idx+0.5) / u_trans_num).x;
+    vec3 grad_re = vec3(0.0);
+    vec3 grad_im = vec3(0.0);
+    int num = int(u_trans_num);
+    for(int idx = 0; idx < num; idx++){
+        ivec2 st = source_texel(idx);
+        vec3 tp = texelFetch(u_trans_pos, st, 0).xyz;
+        float p = texelFetch(u_trans_phase, st, 0).x;
+        vec3 dir = texelFetch(u_trans_dir, st, 0).xyz;
+        float amp = texelFetch(u_trans_amp, st, 0).x;
+        amp *= directivity(dir, v_gpos - tp);
         float d = length(v_gpos - tp);
-        im += cos(p - WAVE_NUM*d) / d;
-        re += sin(p - WAVE_NUM*d) / d;
+        amp *= exp(-u_attenuation * d);
+        float sre = amp * cos(p - u_wave_num*d) / d;
+        float sim = amp * sin(p - u_wave_num*d) / d;
+        re += sre;
+        im += sim;
+        if (u_quantity == 5) {
+            vec3 r = (v_gpos - tp) / d;
+            grad_re += r * (-sre / d + u_wave_num * sim);
+            grad_im += r * (-sim / d - u_wave_num * sre);
+        }
+    }
+    if (u_quantity == 5) {
+        float u = u_gorkov_k1 * (re*re + im*im) - u_gorkov_k2 * (dot(grad_re, grad_re) + dot(grad_im, grad_im));
+        o_Color = coloring(0.5 + 0.5 * u * u_color_scale);
+        return;
     }
-    float c = sqrt(re*re + im*im);
-    o_Color = coloring(c);
+    float c = cos(u_time_phase);
+    float s = sin(u_time_phase);
+    o_Color = coloring(quantity(re * c - im * s, re * s + im * c));
 }
\ No newline at end of file
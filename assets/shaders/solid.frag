@@ -0,0 +1,6 @@
+#version 150 core
+out vec4 o_Color;
+uniform vec4 u_color;
+void main() {
+    o_Color = u_color;
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+/*
+ * File: devices.rs
+ * Project: examples
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+extern crate acoustic_field_viewer;
+
+use std::f32::consts::PI;
+
+use acoustic_field_viewer::coloring_method::coloring_hsv;
+use acoustic_field_viewer::geometry::{self, Device};
+use acoustic_field_viewer::view::event::*;
+use acoustic_field_viewer::view::{
+    AcousticFiledSliceViewer, SoundSourceViewer, UpdateHandler, ViewWindow, ViewerSettings,
+};
+
+pub fn main() {
+    const TRANS_SIZE: f32 = geometry::AUTD_TRANS_SIZE;
+    const DISTANCE: f32 = 200.;
+
+    let center = [TRANS_SIZE * 8.5, TRANS_SIZE * 6.5, DISTANCE / 2.];
+
+    // Two boards facing each other: the upper one is flipped around the y axis.
+    let width = TRANS_SIZE * (geometry::AUTD_NUM_TRANS_X - 1) as f32;
+    let devices = vec![
+        Device::new(geometry::autd_device(), [0., 0., 0.], (1., [0., 0., 0.])),
+        Device::new(
+            geometry::autd_device(),
+            [width, 0., DISTANCE],
+            quaternion::axis_angle([0., 1., 0.], PI),
+        ),
+    ];
+
+    let settings = ViewerSettings::new(
+        40e3,
+        TRANS_SIZE,
+        coloring_hsv,
+        scarlet::colormap::ListedColorMap::inferno(),
+    );
+
+    let mut acoustic_field_viewer = AcousticFiledSliceViewer::new();
+    acoustic_field_viewer.translate(center);
+
+    let mut selected = 0;
+    let update = move |update_handler: &mut UpdateHandler, button: Option<Button>| {
+        let travel = 5.0;
+        match button {
+            Some(Button::Keyboard(Key::D1)) => selected = 0,
+            Some(Button::Keyboard(Key::D2)) => selected = 1,
+            Some(Button::Keyboard(Key::Up)) => {
+                update_handler.translate_device(selected, [0., travel, 0.])
+            }
+            Some(Button::Keyboard(Key::Down)) => {
+                update_handler.translate_device(selected, [0., -travel, 0.])
+            }
+            Some(Button::Keyboard(Key::Left)) => {
+                update_handler.translate_device(selected, [-travel, 0., 0.])
+            }
+            Some(Button::Keyboard(Key::Right)) => {
+                update_handler.translate_device(selected, [travel, 0., 0.])
+            }
+            Some(Button::Keyboard(Key::Z)) => {
+                update_handler.rotate_device(selected, [0., 1., 0.], 0.05)
+            }
+            Some(Button::Keyboard(Key::X)) => {
+                update_handler.rotate_device(selected, [0., 1., 0.], -0.05)
+            }
+            _ => return,
+        }
+        update_handler.highlight_device(Some(selected));
+    };
+
+    let mut window = ViewWindow::with_devices(
+        devices,
+        SoundSourceViewer::new(),
+        acoustic_field_viewer,
+        settings,
+    )
+    .unwrap();
+    window.update = Some(update);
+    window.start();
+}
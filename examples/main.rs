@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 01/05/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -13,36 +13,22 @@
 
 extern crate acoustic_field_viewer;
 
-use std::f32::consts::PI;
-
 use acoustic_field_viewer::coloring_method::coloring_hsv;
-use acoustic_field_viewer::sound_source::SoundSource;
-use acoustic_field_viewer::vec_utils;
+use acoustic_field_viewer::field::{Directivity, FieldQuantity, Reflector};
+use acoustic_field_viewer::geometry;
+use acoustic_field_viewer::phase::{self, AmplitudeConstraint};
 use acoustic_field_viewer::view::event::*;
 use acoustic_field_viewer::view::{
     AcousticFiledSliceViewer, SoundSourceViewer, UpdateHandler, ViewWindow, ViewerSettings,
 };
 
 pub fn main() {
-    const NUM_TRANS_X: usize = 18;
-    const NUM_TRANS_Y: usize = 14;
-    const TRANS_SIZE: f32 = 10.18;
-    const WAVE_LENGTH: f32 = 8.5;
+    const NUM_TRANS_X: usize = geometry::AUTD_NUM_TRANS_X;
+    const NUM_TRANS_Y: usize = geometry::AUTD_NUM_TRANS_Y;
+    const TRANS_SIZE: f32 = geometry::AUTD_TRANS_SIZE;
 
     let mut focal_pos = [TRANS_SIZE * 8.5, TRANS_SIZE * 6.5, 150.];
 
-    let mut transducers = Vec::new();
-    let zdir = [0., 0., 1.];
-    for y in 0..NUM_TRANS_Y {
-        for x in 0..NUM_TRANS_X {
-            let pos = [TRANS_SIZE * x as f32, TRANS_SIZE * y as f32, 0.];
-            let d = vec_utils::dist(pos, focal_pos);
-            let phase = (d % WAVE_LENGTH) / WAVE_LENGTH;
-            let phase = 2.0 * PI * phase;
-            transducers.push(SoundSource::new(pos, zdir, phase));
-        }
-    }
-
     let mut settings = ViewerSettings::new(
         40e3,
         TRANS_SIZE,
@@ -51,12 +37,21 @@ pub fn main() {
     );
     settings.color_scale = 0.6;
     settings.slice_alpha = 0.95;
+    settings.directivity = Directivity::T4010A1;
+
+    let mut transducers = geometry::rect_grid(NUM_TRANS_X, NUM_TRANS_Y, TRANS_SIZE, &[]).unwrap();
+    phase::focus(&mut transducers, focal_pos, &settings);
 
     let source_viewer = SoundSourceViewer::new();
     let mut acoustic_field_viewer = AcousticFiledSliceViewer::new();
     acoustic_field_viewer.translate(focal_pos);
 
     let update = |update_handler: &mut UpdateHandler, button: Option<Button>| {
+        match update_handler.take_capture_result() {
+            Some(Ok(path)) => println!("Captured {}", path.display()),
+            Some(Err(err)) => eprintln!("Failed to capture: {}", err),
+            None => (),
+        }
         let travel = 5.0;
         match button {
             Some(Button::Keyboard(Key::Up)) => {
@@ -70,68 +65,159 @@ pub fn main() {
                 update_handler.update_position();
             }
             Some(Button::Keyboard(Key::Left)) => {
-                update_handler
-                    .field_slice_viewer
-                    .translate([-travel, 0., 0.]);
+                update_handler.slice_mut(0).translate([-travel, 0., 0.]);
             }
             Some(Button::Keyboard(Key::Right)) => {
-                update_handler
-                    .field_slice_viewer
-                    .translate([travel, 0., 0.]);
+                update_handler.slice_mut(0).translate([travel, 0., 0.]);
             }
             Some(Button::Keyboard(Key::Z)) => {
-                update_handler.field_slice_viewer.rotate([0., 0., 1.], 0.05);
+                update_handler.slice_mut(0).rotate([0., 0., 1.], 0.05);
             }
             Some(Button::Keyboard(Key::X)) => {
-                update_handler
-                    .field_slice_viewer
-                    .rotate([0., 0., 1.], -0.05);
+                update_handler.slice_mut(0).rotate([0., 0., 1.], -0.05);
             }
             Some(Button::Keyboard(Key::C)) => {
                 update_handler.settings.borrow_mut().color_scale += 0.1;
-                update_handler.field_slice_viewer.update_color_map();
+                update_handler.update_color_map();
             }
             Some(Button::Keyboard(Key::V)) => {
                 update_handler.settings.borrow_mut().color_scale -= 0.1;
-                update_handler.field_slice_viewer.update_color_map();
+                update_handler.update_color_map();
+            }
+            Some(Button::Keyboard(Key::Q)) => {
+                {
+                    let mut settings = update_handler.settings.borrow_mut();
+                    settings.field_quantity = match settings.field_quantity {
+                        FieldQuantity::Amplitude => FieldQuantity::RealPart,
+                        FieldQuantity::RealPart => FieldQuantity::Phase,
+                        FieldQuantity::Phase => FieldQuantity::Intensity,
+                        FieldQuantity::Intensity => FieldQuantity::GorkovPotential,
+                        _ => FieldQuantity::Amplitude,
+                    };
+                }
+                update_handler.update_settings();
+            }
+            Some(Button::Keyboard(Key::T)) => {
+                {
+                    let mut settings = update_handler.settings.borrow_mut();
+                    settings.animate = !settings.animate;
+                    settings.field_quantity = FieldQuantity::RealPart;
+                }
+                update_handler.update_settings();
+            }
+            Some(Button::Keyboard(Key::K)) => {
+                {
+                    let mut settings = update_handler.settings.borrow_mut();
+                    settings.show_force = !settings.show_force;
+                }
+                update_handler.update_settings();
+            }
+            Some(Button::Keyboard(Key::R)) => {
+                {
+                    let mut settings = update_handler.settings.borrow_mut();
+                    if settings.reflectors.is_empty() {
+                        let center = [TRANS_SIZE * 8.5, TRANS_SIZE * 6.5, 200.];
+                        settings
+                            .reflectors
+                            .push(Reflector::new(center, [0., 0., -1.], 1.0));
+                    } else {
+                        settings.reflectors.clear();
+                    }
+                }
+                update_handler.update_settings();
+            }
+            Some(Button::Keyboard(Key::N)) => {
+                let mut slice = AcousticFiledSliceViewer::new();
+                slice.set_plane(
+                    [0., 0., focal_pos[2]],
+                    [TRANS_SIZE * (NUM_TRANS_X - 1) as f32, 0., 0.],
+                    [0., TRANS_SIZE * (NUM_TRANS_Y - 1) as f32, 0.],
+                );
+                slice.set_field_quantity(Some(FieldQuantity::Phase));
+                slice.set_alpha(Some(0.6));
+                update_handler.add_slice(slice);
+            }
+            Some(Button::Keyboard(Key::B)) => {
+                let num = update_handler.num_slices();
+                if num > 1 {
+                    update_handler.remove_slice(num - 1);
+                }
+            }
+            Some(Button::Keyboard(Key::P)) => {
+                update_handler.capture_supersampled(2);
+            }
+            Some(Button::Keyboard(Key::E)) => {
+                let field = update_handler.slice(0).sample_field(200, 200);
+                field.write_csv("slice.csv").unwrap();
+                field.write_npy("slice.npy").unwrap();
             }
             Some(Button::Keyboard(Key::G)) => {
                 focal_pos = vecmath::vec3_add(focal_pos, [travel, 0., 0.]);
-                let dist = |l: vecmath::Vector3<f32>, r: vecmath::Vector3<f32>| {
-                    let d = vecmath::vec3_sub(l, r);
-                    vecmath::vec3_dot(d, d).sqrt()
-                };
-                for source in update_handler.sources.borrow_mut().iter_mut() {
-                    let pos = source.pos;
-                    let d = dist(pos, focal_pos);
-                    let phase = (d % WAVE_LENGTH) / WAVE_LENGTH;
-                    let phase = 2.0 * PI * phase;
-
-                    source.phase = phase;
-                }
+                phase::focus(
+                    &mut update_handler.sources.borrow_mut(),
+                    focal_pos,
+                    &update_handler.settings.borrow(),
+                );
+                update_handler.update_phase();
+            }
+            Some(Button::Keyboard(Key::M)) => {
+                let foci = [
+                    vecmath::vec3_add(focal_pos, [-20., 0., 0.]),
+                    vecmath::vec3_add(focal_pos, [20., 0., 0.]),
+                ];
+                phase::gspat(
+                    &mut update_handler.sources.borrow_mut(),
+                    &foci,
+                    &[1., 1.],
+                    100,
+                    AmplitudeConstraint::Normalize(1.),
+                    &update_handler.settings.borrow(),
+                )
+                .unwrap();
+                update_handler.update_phase();
+            }
+            Some(Button::Keyboard(Key::D1)) => {
+                phase::twin_trap(
+                    &mut update_handler.sources.borrow_mut(),
+                    focal_pos,
+                    [1., 0., 0.],
+                    &update_handler.settings.borrow(),
+                );
+                update_handler.update_phase();
+            }
+            Some(Button::Keyboard(Key::D2)) => {
+                phase::vortex_trap(
+                    &mut update_handler.sources.borrow_mut(),
+                    focal_pos,
+                    [0., 0., 1.],
+                    &update_handler.settings.borrow(),
+                );
+                update_handler.update_phase();
+            }
+            Some(Button::Keyboard(Key::D3)) => {
+                phase::bottle_beam(
+                    &mut update_handler.sources.borrow_mut(),
+                    focal_pos,
+                    [0., 0., 1.],
+                    &update_handler.settings.borrow(),
+                );
                 update_handler.update_phase();
             }
             Some(Button::Keyboard(Key::F)) => {
                 focal_pos = vecmath::vec3_add(focal_pos, [-travel, 0., 0.]);
-                let dist = |l: vecmath::Vector3<f32>, r: vecmath::Vector3<f32>| {
-                    let d = vecmath::vec3_sub(l, r);
-                    vecmath::vec3_dot(d, d).sqrt()
-                };
-                for source in update_handler.sources.borrow_mut().iter_mut() {
-                    let pos = source.pos;
-                    let d = dist(pos, focal_pos);
-                    let phase = (d % WAVE_LENGTH) / WAVE_LENGTH;
-                    let phase = 2.0 * PI * phase;
-
-                    source.phase = phase;
-                }
+                phase::focus(
+                    &mut update_handler.sources.borrow_mut(),
+                    focal_pos,
+                    &update_handler.settings.borrow(),
+                );
                 update_handler.update_phase();
             }
             _ => (),
         }
     };
 
-    let mut window = ViewWindow::new(transducers, source_viewer, acoustic_field_viewer, settings);
+    let mut window =
+        ViewWindow::new(transducers, source_viewer, acoustic_field_viewer, settings).unwrap();
     window.update = Some(update);
     window.start();
 }
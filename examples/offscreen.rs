@@ -0,0 +1,54 @@
+/*
+ * File: offscreen.rs
+ * Project: examples
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+extern crate acoustic_field_viewer;
+
+use acoustic_field_viewer::coloring_method::coloring_hsv;
+use acoustic_field_viewer::geometry;
+use acoustic_field_viewer::phase;
+use acoustic_field_viewer::view::{
+    AcousticFiledSliceViewer, OffscreenRenderer, SoundSourceViewer, ViewerSettings,
+};
+
+pub fn main() {
+    const NUM_TRANS_X: usize = geometry::AUTD_NUM_TRANS_X;
+    const NUM_TRANS_Y: usize = geometry::AUTD_NUM_TRANS_Y;
+    const TRANS_SIZE: f32 = geometry::AUTD_TRANS_SIZE;
+
+    let mut settings = ViewerSettings::new(
+        40e3,
+        TRANS_SIZE,
+        coloring_hsv,
+        scarlet::colormap::ListedColorMap::inferno(),
+    );
+    settings.color_scale = 0.6;
+    settings.slice_alpha = 0.95;
+
+    let focal_pos = [TRANS_SIZE * 8.5, TRANS_SIZE * 6.5, 150.];
+
+    let mut transducers = geometry::rect_grid(NUM_TRANS_X, NUM_TRANS_Y, TRANS_SIZE, &[]).unwrap();
+    phase::focus(&mut transducers, focal_pos, &settings);
+
+    let mut acoustic_field_viewer = AcousticFiledSliceViewer::new();
+    acoustic_field_viewer.translate(focal_pos);
+
+    let mut renderer = OffscreenRenderer::new(
+        transducers,
+        SoundSourceViewer::new(),
+        acoustic_field_viewer,
+        settings,
+        [1280, 960],
+    )
+    .unwrap();
+    renderer.render_to_file("field.png").unwrap();
+}
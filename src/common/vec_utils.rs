@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 28/04/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -34,6 +34,10 @@ pub fn to_vec4(v: Vector3) -> Vector4 {
     [v[0], v[1], v[2], 0.]
 }
 
+pub fn to_point4(v: Vector3) -> Vector4 {
+    [v[0], v[1], v[2], 1.]
+}
+
 pub fn dist(l: Vector3, r: Vector3) -> f32 {
     let d = vecmath::vec3_sub(l, r);
     vecmath::vec3_dot(d, d).sqrt()
@@ -48,6 +52,15 @@ pub fn mat4_scale(s: f32) -> Matrix4 {
     ]
 }
 
+pub fn mat4_scale3(s: Vector3) -> Matrix4 {
+    [
+        [s[0], 0., 0., 0.],
+        [0., s[1], 0., 0.],
+        [0., 0., s[2], 0.],
+        [0., 0., 0., 1.],
+    ]
+}
+
 pub fn mat4_t(pos: Vector3) -> Matrix4 {
     [
         [1., 0., 0., 0.],
@@ -73,7 +86,7 @@ pub fn mat4_rot(rot: Quaterion) -> Matrix4 {
     let w = rot.0;
     [
         [
-            1. - 2. * y * y,
+            1. - 2. * y * y - 2. * z * z,
             2. * x * y + 2. * w * z,
             2. * x * z - 2. * w * y,
             0.,
@@ -120,6 +133,72 @@ pub fn quaternion_to(vec: Vector3, to: Vector3) -> Quaterion {
     }
 }
 
+/// Two unit vectors perpendicular to `dir` and to each other.
+pub fn orthonormal_basis(dir: Vector3) -> (Vector3, Vector3) {
+    let n = vecmath::vec3_normalized(dir);
+    let a = if n[0].abs() < 0.9 {
+        [1., 0., 0.]
+    } else {
+        [0., 1., 0.]
+    };
+    let u = vecmath::vec3_normalized(vecmath::vec3_cross(a, n));
+    let v = vecmath::vec3_cross(n, u);
+    (u, v)
+}
+
 pub fn vec3_is_valid(v: Vector3) -> bool {
     !v[0].is_nan() && !v[1].is_nan() && !v[2].is_nan()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mat4_rot_matches_quaternion_rotation() {
+        // A half turn about z must flip x; [0][0] used to miss the z term and come out as 1.
+        let m = mat4_rot(quaternion::axis_angle([0., 0., 1.], std::f32::consts::PI));
+        assert!((m[0][0] + 1.).abs() < 1e-6);
+
+        for &(axis, angle) in &[
+            ([1., 0., 0.], 0.3),
+            ([0., 1., 0.], 1.2),
+            ([0., 0., 1.], -2.0),
+            ([1., -2., 0.5], 0.7),
+        ] {
+            let q = quaternion::axis_angle(vecmath::vec3_normalized(axis), angle);
+            let m = mat4_rot(q);
+            for &v in &[[1., 0., 0.], [0., 1., 0.], [0., 0., 1.], [0.3, -0.4, 2.]] {
+                let r = vecmath::col_mat4_transform(m, to_vec4(v));
+                let expected = quaternion::rotate_vector(q, v);
+                assert!(
+                    dist([r[0], r[1], r[2]], expected) < 1e-5,
+                    "{:?} != {:?}",
+                    r,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn orthonormal_basis_is_orthonormal() {
+        for &dir in &[
+            [0., 0., 1.],
+            [1., 0., 0.],
+            [0., -3., 0.],
+            [1., 2., -2.],
+            [0.95, 0.1, 0.],
+        ] {
+            let n = vecmath::vec3_normalized(dir);
+            let (u, v) = orthonormal_basis(dir);
+            assert!((vecmath::vec3_len(u) - 1.).abs() < 1e-5);
+            assert!((vecmath::vec3_len(v) - 1.).abs() < 1e-5);
+            assert!(vecmath::vec3_dot(u, v).abs() < 1e-5);
+            assert!(vecmath::vec3_dot(u, n).abs() < 1e-5);
+            assert!(vecmath::vec3_dot(v, n).abs() < 1e-5);
+            // Right-handed: u x v = n.
+            assert!(dist(vecmath::vec3_cross(u, v), n) < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+/*
+ * File: calculator.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use crate::field::reflector::for_each_image;
+use crate::field::Complex;
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+use crate::view::ViewerSettings;
+
+/// Complex sound pressure at `point` radiated by `sources`.
+///
+/// Every source is treated as a spherical wave `amp * D(theta) * exp(i(phase - kd)) / d`,
+/// where `D` is `settings.directivity`, which is the same superposition evaluated in `slice.frag`.
+/// Reflections from `settings.reflectors` are added as image sources.
+pub fn calc_pressure(
+    point: Vector3,
+    sources: &[SoundSource],
+    settings: &ViewerSettings,
+) -> Complex {
+    sources
+        .iter()
+        .map(|source| {
+            transfer(point, source, settings) * Complex::from_polar(&source.amp, &source.phase)
+        })
+        .sum()
+}
+
+/// Pressure at `point` from `source` driven with unit amplitude and zero phase.
+pub(crate) fn transfer(point: Vector3, source: &SoundSource, settings: &ViewerSettings) -> Complex {
+    let unit = SoundSource::with_amp(source.pos, source.dir, 0., 1.);
+    let mut p = Complex::new(0., 0.);
+    for_each_image(
+        &unit,
+        &settings.reflectors,
+        settings.reflection_order,
+        &mut |image| p += free_field(point, image, settings) * image.amp,
+    );
+    p
+}
+
+/// `transfer` without reflections.
+fn free_field(point: Vector3, source: &SoundSource, settings: &ViewerSettings) -> Complex {
+    let wave_num = settings.wave_num();
+    let r = vecmath::vec3_sub(point, source.pos);
+    let d = vecmath::vec3_len(r);
+    let cos_theta = vecmath::vec3_dot(vecmath::vec3_normalized(source.dir), r) / d;
+    let theta = cos_theta.clamp(-1.0, 1.0).acos();
+    let gain = settings.directivity.gain(theta, wave_num);
+    let attenuation =
+        (-settings.medium.attenuation_coefficient(settings.freqency) * d * 1e-3).exp();
+    Complex::from_polar(&(gain * attenuation / d), &(-wave_num * d))
+}
+
+/// Gradient of `calc_pressure` with respect to `point`, per mm.
+///
+/// Like `slice.frag`, the angular variation of the directivity is neglected,
+/// so every source contributes `p (-1/d - ik) r / d`.
+pub fn calc_pressure_gradient(
+    point: Vector3,
+    sources: &[SoundSource],
+    settings: &ViewerSettings,
+) -> [Complex; 3] {
+    let wave_num = settings.wave_num();
+    let mut grad = [Complex::new(0., 0.); 3];
+    for source in sources {
+        for_each_image(
+            source,
+            &settings.reflectors,
+            settings.reflection_order,
+            &mut |image| {
+                let r = vecmath::vec3_sub(point, image.pos);
+                let d = vecmath::vec3_len(r);
+                let p = free_field(point, image, settings)
+                    * Complex::from_polar(&image.amp, &image.phase);
+                let dp = p * Complex::new(-1.0 / d, -wave_num);
+                for (g, r) in grad.iter_mut().zip(r.iter()) {
+                    *g += dp * (r / d);
+                }
+            },
+        );
+    }
+    grad
+}
+
+pub fn calc_field(
+    points: &[Vector3],
+    sources: &[SoundSource],
+    settings: &ViewerSettings,
+) -> Vec<Complex> {
+    points
+        .iter()
+        .map(|&point| calc_pressure(point, sources, settings))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::phase;
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    fn grid() -> Vec<SoundSource> {
+        (0..25)
+            .map(|i| {
+                let pos = [10.18 * (i % 5) as f32, 10.18 * (i / 5) as f32, 0.];
+                SoundSource::with_amp(pos, [0., 0., 1.], 0., 0.5 + 0.1 * (i % 3) as f32)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn single_source_decays_with_distance() {
+        let settings = settings();
+        let source = SoundSource::with_amp([1., 2., 3.], [0., 0., 1.], 0.7, 2.0);
+        for &d in &[10f32, 50., 200.] {
+            let p = calc_pressure([1., 2., 3. + d], &[source], &settings);
+            assert!((p.norm() - 2.0 / d).abs() < 1e-5 * 2.0 / d);
+        }
+    }
+
+    #[test]
+    fn focus_adds_up_amplitudes() {
+        let settings = settings();
+        let mut sources = grid();
+        let focal_pos = [20., 20., 150.];
+        phase::focus(&mut sources, focal_pos, &settings);
+
+        let expected: f32 = sources
+            .iter()
+            .map(|s| s.amp / crate::vec_utils::dist(s.pos, focal_pos))
+            .sum();
+        let p = calc_pressure(focal_pos, &sources, &settings);
+        assert!((p.norm() - expected).abs() < 1e-4 * expected);
+    }
+
+    #[test]
+    fn matches_shader_convention() {
+        let settings = settings();
+        let mut sources = grid();
+        phase::focus(&mut sources, [20., 20., 150.], &settings);
+
+        let k = settings.wave_num();
+        let point = [35., -10., 80.];
+        let (re, im) = sources.iter().fold((0f32, 0f32), |(re, im), s| {
+            let d = crate::vec_utils::dist(s.pos, point);
+            (
+                re + s.amp * (s.phase - k * d).cos() / d,
+                im + s.amp * (s.phase - k * d).sin() / d,
+            )
+        });
+        let p = calc_pressure(point, &sources, &settings);
+        assert!((p.re - re).abs() < 1e-4);
+        assert!((p.im - im).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,246 @@
+/*
+ * File: directivity.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::f32::consts::PI;
+
+const T4010A1_COEF_A: [f32; 9] = [
+    1.0,
+    1.0,
+    1.0,
+    0.891_250_9,
+    0.707_945_8,
+    0.501_187_2,
+    0.354_813_4,
+    0.251_188_6,
+    0.199_526_2,
+];
+const T4010A1_COEF_B: [f32; 9] = [
+    0.,
+    0.,
+    -0.004_596_481,
+    -0.015_552_08,
+    -0.020_811_48,
+    -0.018_221_12,
+    -0.012_243_75,
+    -0.007_803_456,
+    -0.003_128_575,
+];
+const T4010A1_COEF_C: [f32; 9] = [
+    0.,
+    0.,
+    -0.000_787_968_1,
+    -0.000_307_591_5,
+    -0.000_218_348_6,
+    0.000_477_384_2,
+    0.000_120_353_1,
+    0.000_323_676_3,
+    0.000_143_850_5,
+];
+const T4010A1_COEF_D: [f32; 9] = [
+    0.,
+    0.,
+    1.601_255e-5,
+    2.974_762e-6,
+    2.319_109e-5,
+    -1.190_103e-5,
+    6.777_437e-6,
+    -5.995_48e-6,
+    -4.793_728e-6,
+];
+
+/// Amplitude gain of a source as a function of the angle from its `dir`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Directivity {
+    Omni,
+    /// Baffled circular piston of the given radius in mm.
+    Piston {
+        radius: f32,
+    },
+    /// Measured directivity of Nippon Ceramic T4010A1, spline interpolated every 10 deg.
+    T4010A1,
+}
+
+impl Directivity {
+    /// `theta` is in radian and `wave_num` is in 1/mm.
+    /// The gain is signed: the side lobes of `Piston` are negative, i.e. flipped by PI in phase.
+    pub fn gain(&self, theta: f32, wave_num: f32) -> f32 {
+        match self {
+            Directivity::Omni => 1.0,
+            Directivity::Piston { radius } => {
+                // The baffle blocks the radiation behind the piston.
+                if theta.abs() > PI / 2.0 {
+                    return 0.0;
+                }
+                let x = wave_num * radius * theta.sin();
+                if x.abs() < 1e-6 {
+                    1.0
+                } else {
+                    (2.0 * bessel_j1(x as f64) / x as f64) as f32
+                }
+            }
+            Directivity::T4010A1 => t4010a1(theta.to_degrees()),
+        }
+    }
+
+    /// Samples the gain at `n` equally spaced angles over [0, PI].
+    pub fn table(&self, n: usize, wave_num: f32) -> Vec<f32> {
+        (0..n)
+            .map(|i| self.gain(PI * i as f32 / (n - 1) as f32, wave_num))
+            .collect()
+    }
+}
+
+fn t4010a1(theta_deg: f32) -> f32 {
+    let mut theta_deg = theta_deg.abs();
+    while theta_deg > 90.0 {
+        theta_deg = (180.0 - theta_deg).abs();
+    }
+    let i = (theta_deg / 10.0).ceil() as usize;
+    if i == 0 {
+        return 1.0;
+    }
+    let a = T4010A1_COEF_A[i - 1];
+    let b = T4010A1_COEF_B[i - 1];
+    let c = T4010A1_COEF_C[i - 1];
+    let d = T4010A1_COEF_D[i - 1];
+    let x = theta_deg - (i - 1) as f32 * 10.0;
+    a + b * x + c * x * x + d * x * x * x
+}
+
+// Polynomial approximation of the Bessel function of the first kind of order one
+// (Numerical Recipes, bessj1).
+fn bessel_j1(x: f64) -> f64 {
+    let ax = x.abs();
+    if ax < 8.0 {
+        let y = x * x;
+        let n = x
+            * (72_362_614_232.0
+                + y * (-7_895_059_235.0
+                    + y * (242_396_853.1
+                        + y * (-2_972_611.439 + y * (15_704.482_60 + y * (-30.160_366_06))))));
+        let d = 144_725_228_442.0
+            + y * (2_300_535_178.0
+                + y * (18_583_304.74 + y * (99_447.433_94 + y * (376.999_139_7 + y))));
+        n / d
+    } else {
+        let z = 8.0 / ax;
+        let y = z * z;
+        let xx = ax - 3.0 * std::f64::consts::FRAC_PI_4;
+        let p = 1.0
+            + y * (0.183_105e-2
+                + y * (-0.351_639_649_6e-4 + y * (0.245_752_017_3e-5 + y * (-0.240_337_019e-6))));
+        let q = 0.046_874_999_95
+            + y * (-0.200_269_087_3e-3
+                + y * (0.844_919_909_6e-5 + y * (-0.882_289_87e-6 + y * 0.105_787_412e-6)));
+        let ans = (std::f64::consts::FRAC_2_PI / ax).sqrt() * (xx.cos() * p - z * xx.sin() * q);
+        if x < 0.0 {
+            -ans
+        } else {
+            ans
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WAVE_NUM: f32 = 2. * PI / 8.5;
+    const MODELS: [Directivity; 3] = [
+        Directivity::Omni,
+        Directivity::Piston { radius: 5. },
+        Directivity::T4010A1,
+    ];
+
+    #[test]
+    fn on_axis_gain_is_one() {
+        for model in &MODELS {
+            assert_eq!(model.gain(0., WAVE_NUM), 1.0, "{:?}", model);
+        }
+    }
+
+    #[test]
+    fn gain_is_symmetric() {
+        for model in &MODELS {
+            for &theta in &[0.1f32, 0.35, 0.8, 1.2, 1.5, 2.5] {
+                let l = model.gain(theta, WAVE_NUM);
+                let r = model.gain(-theta, WAVE_NUM);
+                assert!(
+                    (l - r).abs() < 1e-6,
+                    "{:?} at {}: {} != {}",
+                    model,
+                    theta,
+                    l,
+                    r
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn t4010a1_passes_through_knots() {
+        // Datasheet levels in dB every 10 deg from 0 to 90 deg.
+        let knots_db = [0., 0., 0., -1., -3., -6., -9., -12., -14., -15.];
+        for (k, db) in knots_db.iter().enumerate() {
+            let expected = 10f32.powf(db / 20.);
+            let theta = (10. * k as f32).to_radians();
+            let gain = Directivity::T4010A1.gain(theta, WAVE_NUM);
+            assert!(
+                (gain - expected).abs() < 1e-4,
+                "{} deg: {} != {}",
+                10 * k,
+                gain,
+                expected
+            );
+            // Continuous across the knot.
+            let next = Directivity::T4010A1.gain(theta + 1e-4, WAVE_NUM);
+            assert!((next - gain).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn t4010a1_interpolates_between_knots() {
+        for k in 2..9 {
+            let lower = Directivity::T4010A1.gain((10. * (k + 1) as f32).to_radians(), WAVE_NUM);
+            let upper = Directivity::T4010A1.gain((10. * k as f32).to_radians(), WAVE_NUM);
+            let mid = Directivity::T4010A1.gain((10. * k as f32 + 5.).to_radians(), WAVE_NUM);
+            assert!(
+                lower < mid && mid < upper,
+                "{} < {} < {}",
+                lower,
+                mid,
+                upper
+            );
+        }
+    }
+
+    #[test]
+    fn piston_first_null_and_baffle() {
+        // Large enough for ka to exceed the first zero of J1 at 3.8317.
+        let radius = 8.;
+        let piston = Directivity::Piston { radius };
+        let theta = (3.8317 / (WAVE_NUM * radius)).asin();
+        assert!(piston.gain(theta, WAVE_NUM).abs() < 1e-4);
+        assert!(piston.gain(theta + 0.1, WAVE_NUM) < 0.);
+        assert_eq!(piston.gain(PI / 2. + 0.01, WAVE_NUM), 0.);
+    }
+
+    #[test]
+    fn table_spans_zero_to_pi() {
+        let table = Directivity::T4010A1.table(181, WAVE_NUM);
+        assert_eq!(table.len(), 181);
+        assert_eq!(table[0], 1.0);
+        assert!((table[30] - Directivity::T4010A1.gain(PI / 6., WAVE_NUM)).abs() < 1e-6);
+        assert!((table[180] - table[0]).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,198 @@
+/*
+ * File: gorkov.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::f32::consts::PI;
+
+use crate::field::{calc_pressure, calc_pressure_gradient};
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+use crate::view::ViewerSettings;
+
+/// Small sphere levitated in the field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    /// Radius in mm.
+    pub radius: f32,
+    /// Density in kg/m^3.
+    pub density: f32,
+    /// Speed of sound inside the particle in m/s.
+    pub speed_of_sound: f32,
+}
+
+impl Particle {
+    /// Expanded polystyrene bead, the usual test particle for levitators.
+    pub fn polystyrene(radius: f32) -> Particle {
+        Particle {
+            radius,
+            density: 25.,
+            speed_of_sound: 900.,
+        }
+    }
+
+    /// Coefficients `(k1, k2)` such that the Gor'kov potential is proportional to
+    /// `k1 |p|^2 - k2 |grad p|^2`, with the gradient per mm.
+    pub(crate) fn gorkov_coefficients(&self, settings: &ViewerSettings) -> (f32, f32) {
+        let c0 = settings.medium.sound_speed;
+        let rho0 = settings.medium.density;
+        let f1 = 1. - (rho0 * c0 * c0) / (self.density * self.speed_of_sound * self.speed_of_sound);
+        let f2 = 2. * (self.density - rho0) / (2. * self.density + rho0);
+        let wave_num = settings.wave_num();
+        (f1, 1.5 * f2 / (wave_num * wave_num))
+    }
+
+    /// `V / (4 rho0 c0^2)` with the volume in m^3.
+    fn gorkov_scale(&self, settings: &ViewerSettings) -> f32 {
+        let c0 = settings.medium.sound_speed;
+        let r = self.radius * 1e-3;
+        let volume = 4. / 3. * PI * r * r * r;
+        volume / (4. * settings.medium.density * c0 * c0)
+    }
+}
+
+impl Default for Particle {
+    fn default() -> Self {
+        Particle::polystyrene(1.)
+    }
+}
+
+/// Gor'kov potential of `particle` at `point`.
+///
+/// Source amplitudes are unitless, so the result is in the same arbitrary scale
+/// as `calc_pressure` squared, times m^3/Pa.
+pub fn calc_gorkov_potential(
+    point: Vector3,
+    sources: &[SoundSource],
+    particle: &Particle,
+    settings: &ViewerSettings,
+) -> f32 {
+    let (k1, k2) = particle.gorkov_coefficients(settings);
+    let p = calc_pressure(point, sources, settings);
+    let grad = calc_pressure_gradient(point, sources, settings);
+    let grad_sqr: f32 = grad.iter().map(|g| g.norm_sqr()).sum();
+    particle.gorkov_scale(settings) * (k1 * p.norm_sqr() - k2 * grad_sqr)
+}
+
+/// Acoustic radiation force `-grad U` on `particle` at `point`, per mm,
+/// evaluated by central differences of `calc_gorkov_potential`.
+pub fn calc_radiation_force(
+    point: Vector3,
+    sources: &[SoundSource],
+    particle: &Particle,
+    settings: &ViewerSettings,
+) -> Vector3 {
+    let h = settings.wave_length() / 100.;
+    let mut force = [0.; 3];
+    for (axis, f) in force.iter_mut().enumerate() {
+        let mut forward = point;
+        let mut backward = point;
+        forward[axis] += h;
+        backward[axis] -= h;
+        let u_forward = calc_gorkov_potential(forward, sources, particle, settings);
+        let u_backward = calc_gorkov_potential(backward, sources, particle, settings);
+        *f = -(u_forward - u_backward) / (2. * h);
+    }
+    force
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    // Two sources facing each other, symmetric about z = 50.
+    fn standing_wave() -> Vec<SoundSource> {
+        vec![
+            SoundSource::with_amp([0., 0., 0.], [0., 0., 1.], 0., 1.),
+            SoundSource::with_amp([0., 0., 100.], [0., 0., -1.], 0., 1.),
+        ]
+    }
+
+    fn central_difference<F: Fn(Vector3) -> f32>(f: F, point: Vector3, h: f32) -> Vector3 {
+        let mut grad = [0.; 3];
+        for (axis, g) in grad.iter_mut().enumerate() {
+            let mut forward = point;
+            let mut backward = point;
+            forward[axis] += h;
+            backward[axis] -= h;
+            *g = (f(forward) - f(backward)) / (2. * h);
+        }
+        grad
+    }
+
+    #[test]
+    fn potential_matches_finite_difference_gradient() {
+        let settings = settings();
+        let sources = standing_wave();
+        let particle = settings.particle;
+        let (k1, k2) = particle.gorkov_coefficients(&settings);
+        let h = settings.wave_length() / 200.;
+        for &point in &[[1., -2., 40.], [3., 1., 53.], [0., 0., 50.]] {
+            let p = calc_pressure(point, &sources, &settings);
+            let re = central_difference(|x| calc_pressure(x, &sources, &settings).re, point, h);
+            let im = central_difference(|x| calc_pressure(x, &sources, &settings).im, point, h);
+            let grad_sqr: f32 = re.iter().zip(im.iter()).map(|(a, b)| a * a + b * b).sum();
+            let scale = particle.gorkov_scale(&settings);
+            let expected = scale * (k1 * p.norm_sqr() - k2 * grad_sqr);
+            let tolerance = 1e-2 * scale * (k1.abs() * p.norm_sqr() + k2.abs() * grad_sqr);
+
+            let u = calc_gorkov_potential(point, &sources, &particle, &settings);
+            assert!((u - expected).abs() < tolerance, "{} != {}", u, expected);
+        }
+    }
+
+    #[test]
+    fn force_is_minus_gradient_of_potential() {
+        let settings = settings();
+        let sources = standing_wave();
+        let particle = settings.particle;
+        let point = [2., -1., 47.];
+        let grad = central_difference(
+            |x| calc_gorkov_potential(x, &sources, &particle, &settings),
+            point,
+            settings.wave_length() / 400.,
+        );
+
+        let force = calc_radiation_force(point, &sources, &particle, &settings);
+        let norm = vecmath::vec3_len(grad);
+        assert!(norm > 0.);
+        for (f, g) in force.iter().zip(grad.iter()) {
+            assert!((f + g).abs() < 1e-2 * norm, "{:?} != -{:?}", force, grad);
+        }
+    }
+
+    #[test]
+    fn force_vanishes_on_symmetry_plane() {
+        let settings = settings();
+        let sources = standing_wave();
+        let particle = settings.particle;
+        let off_center = calc_radiation_force(
+            [0., 0., 50. + settings.wave_length() / 8.],
+            &sources,
+            &particle,
+            &settings,
+        );
+        let center = calc_radiation_force([0., 0., 50.], &sources, &particle, &settings);
+        let scale = vecmath::vec3_len(off_center);
+        assert!(scale > 0.);
+        assert!(vecmath::vec3_len(center) < 1e-3 * scale);
+    }
+}
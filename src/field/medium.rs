@@ -0,0 +1,173 @@
+/*
+ * File: medium.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+const STANDARD_PRESSURE: f32 = 101_325.;
+const DRY_AIR_GAS_CONSTANT: f32 = 287.05;
+const AIR_HEAT_CAPACITY_RATIO: f32 = 1.4;
+const ZERO_CELSIUS: f32 = 273.15;
+const ISO9613_REFERENCE_TEMPERATURE: f64 = 293.15;
+const ISO9613_TRIPLE_POINT: f64 = 273.16;
+
+/// Medium the waves propagate in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Medium {
+    /// Speed of sound in m/s.
+    pub sound_speed: f32,
+    /// Density in kg/m^3.
+    pub density: f32,
+    /// Amplitude attenuation coefficient in Np/m.
+    pub attenuation: f32,
+    /// Frequency-dependent absorption of air added on top of `attenuation`, if any.
+    pub absorption: Option<Atmosphere>,
+}
+
+/// Atmospheric conditions for the absorption of sound in air after ISO 9613-1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Atmosphere {
+    /// Temperature in degC.
+    pub temperature: f32,
+    /// Relative humidity in %.
+    pub humidity: f32,
+    /// Ambient pressure in Pa.
+    pub pressure: f32,
+}
+
+impl Atmosphere {
+    /// `temperature` degC and `humidity` % relative humidity under standard pressure.
+    pub fn new(temperature: f32, humidity: f32) -> Atmosphere {
+        Atmosphere {
+            temperature,
+            humidity,
+            pressure: STANDARD_PRESSURE,
+        }
+    }
+
+    /// Pure-tone amplitude absorption coefficient in Np/m at `freqency` Hz (ISO 9613-1, eq. 5).
+    pub fn absorption_coefficient(&self, freqency: f32) -> f32 {
+        let f = f64::from(freqency);
+        let t = f64::from(self.temperature + ZERO_CELSIUS);
+        let t_rel = t / ISO9613_REFERENCE_TEMPERATURE;
+        let pa_rel = f64::from(self.pressure / STANDARD_PRESSURE);
+
+        let c = -6.8346 * (ISO9613_TRIPLE_POINT / t).powf(1.261) + 4.6151;
+        let h = f64::from(self.humidity) * 10f64.powf(c) / pa_rel;
+        let fr_o = pa_rel * (24. + 4.04e4 * h * (0.02 + h) / (0.391 + h));
+        let fr_n = pa_rel
+            * t_rel.powf(-0.5)
+            * (9. + 280. * h * (-4.170 * (t_rel.powf(-1. / 3.) - 1.)).exp());
+
+        let alpha = f
+            * f
+            * (1.84e-11 / pa_rel * t_rel.sqrt()
+                + t_rel.powf(-2.5)
+                    * (0.01275 * (-2239.1 / t).exp() / (fr_o + f * f / fr_o)
+                        + 0.1068 * (-3352. / t).exp() / (fr_n + f * f / fr_n)));
+        alpha as f32
+    }
+}
+
+impl Medium {
+    pub fn new(sound_speed: f32, density: f32, attenuation: f32) -> Medium {
+        Medium {
+            sound_speed,
+            density,
+            attenuation,
+            absorption: None,
+        }
+    }
+
+    /// Adds atmospheric absorption under `atmosphere`.
+    pub fn with_absorption(self, atmosphere: Atmosphere) -> Medium {
+        Medium {
+            absorption: Some(atmosphere),
+            ..self
+        }
+    }
+
+    /// Air at 15 degC.
+    pub fn air() -> Medium {
+        Medium::new(340., 1.225, 0.)
+    }
+
+    /// Air at `temperature` degC and `humidity` % relative humidity under standard pressure.
+    ///
+    /// Water vapour is accounted for through the virtual temperature of the moist air.
+    pub fn air_at(temperature: f32, humidity: f32) -> Medium {
+        let t = temperature + ZERO_CELSIUS;
+        let vapour_pressure = humidity / 100. * saturation_vapour_pressure(temperature);
+        let virtual_temperature = t / (1. - vapour_pressure / STANDARD_PRESSURE * (1. - 0.622));
+        Medium::new(
+            (AIR_HEAT_CAPACITY_RATIO * DRY_AIR_GAS_CONSTANT * virtual_temperature).sqrt(),
+            STANDARD_PRESSURE / (DRY_AIR_GAS_CONSTANT * virtual_temperature),
+            0.,
+        )
+    }
+
+    /// Water at 20 degC.
+    pub fn water() -> Medium {
+        Medium::new(1482., 998., 0.)
+    }
+
+    /// Total amplitude attenuation coefficient in Np/m at `freqency` Hz.
+    pub fn attenuation_coefficient(&self, freqency: f32) -> f32 {
+        self.attenuation
+            + self
+                .absorption
+                .map_or(0., |atmosphere| atmosphere.absorption_coefficient(freqency))
+    }
+
+    /// Wavelength in mm at `freqency` Hz.
+    pub fn wave_length(&self, freqency: f32) -> f32 {
+        self.sound_speed * 1e3 / freqency
+    }
+}
+
+impl Default for Medium {
+    fn default() -> Self {
+        Medium::air()
+    }
+}
+
+/// Saturation vapour pressure of water in Pa (Tetens' formula).
+fn saturation_vapour_pressure(temperature: f32) -> f32 {
+    610.78 * (17.27 * temperature / (temperature + 237.3)).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEPER_TO_DB: f32 = 8.685_89;
+
+    // Reference values from ISO 9613-1, Table 1, at 20 degC and 50 % relative humidity.
+    #[test]
+    fn absorption_at_1khz() {
+        let db_per_km = Atmosphere::new(20., 50.).absorption_coefficient(1e3) * NEPER_TO_DB * 1e3;
+        assert!((db_per_km - 4.66).abs() < 0.01, "{} dB/km", db_per_km);
+    }
+
+    #[test]
+    fn absorption_at_40khz() {
+        let db_per_m = Atmosphere::new(20., 50.).absorption_coefficient(40e3) * NEPER_TO_DB;
+        assert!((db_per_m - 1.318).abs() < 0.002, "{} dB/m", db_per_m);
+    }
+
+    #[test]
+    fn absorption_adds_to_attenuation() {
+        let atmosphere = Atmosphere::new(20., 50.);
+        let medium = Medium::new(343., 1.2, 0.5).with_absorption(atmosphere);
+        let expected = 0.5 + atmosphere.absorption_coefficient(40e3);
+        assert!((medium.attenuation_coefficient(40e3) - expected).abs() < 1e-6);
+        assert_eq!(Medium::air().attenuation_coefficient(40e3), 0.);
+    }
+}
@@ -0,0 +1,35 @@
+/*
+ * File: mod.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+mod calculator;
+mod directivity;
+mod gorkov;
+mod medium;
+mod npy;
+mod quantity;
+mod reflector;
+mod slice;
+mod volume;
+mod vtk;
+
+pub(crate) use calculator::transfer;
+pub use calculator::{calc_field, calc_pressure, calc_pressure_gradient};
+pub use directivity::Directivity;
+pub use gorkov::{calc_gorkov_potential, calc_radiation_force, Particle};
+pub use medium::{Atmosphere, Medium};
+pub use quantity::FieldQuantity;
+pub use reflector::{image_sources, Reflector};
+pub use slice::SliceField;
+pub use volume::{write_sources_vtk, VolumeField};
+
+pub type Complex = num_complex::Complex<f32>;
@@ -0,0 +1,134 @@
+/*
+ * File: npy.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::io::{self, Write};
+
+use crate::field::Complex;
+
+/// Writes `data` as a C-ordered complex64 array in NumPy .npy format (version 1.0).
+pub(crate) fn write_complex<W: Write>(
+    writer: &mut W,
+    shape: &[usize],
+    data: &[Complex],
+) -> io::Result<()> {
+    let shape = match shape {
+        [n] => format!("({},)", n),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '<c8', 'fortran_order': False, 'shape': {}, }}",
+        shape
+    );
+    // magic (6) + version (2) + header length (2) + header + '\n' must be a multiple of 64.
+    let unpadded = 10 + header.len() + 1;
+    header.push_str(&" ".repeat((64 - unpadded % 64) % 64));
+    header.push('\n');
+
+    writer.write_all(b"\x93NUMPY")?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for c in data {
+        writer.write_all(&c.re.to_le_bytes())?;
+        writer.write_all(&c.im.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_len(bytes: &[u8]) -> usize {
+        u16::from_le_bytes([bytes[8], bytes[9]]) as usize
+    }
+
+    #[test]
+    fn header_layout() {
+        let mut bytes = vec![];
+        write_complex(&mut bytes, &[2, 3], &[Complex::new(0., 0.); 6]).unwrap();
+
+        assert_eq!(&bytes[..6], b"\x93NUMPY");
+        assert_eq!(&bytes[6..8], &[1, 0]);
+        let len = header_len(&bytes);
+        let header = std::str::from_utf8(&bytes[10..10 + len]).unwrap();
+        assert_eq!(
+            header.trim_end(),
+            "{'descr': '<c8', 'fortran_order': False, 'shape': (2, 3), }"
+        );
+        assert!(header.ends_with('\n'));
+        assert_eq!(bytes.len(), 10 + len + 6 * 8);
+    }
+
+    #[test]
+    fn header_is_padded_to_64_bytes() {
+        // Shapes of different lengths, including ones that need no or almost no padding.
+        for shape in &[
+            vec![1],
+            vec![7],
+            vec![123_456],
+            vec![2, 3],
+            vec![10, 20, 30],
+        ] {
+            for extra in 0..70 {
+                let shape: Vec<usize> = shape.iter().map(|n| n + extra).collect();
+                let mut bytes = vec![];
+                write_complex(&mut bytes, &shape, &[]).unwrap();
+                let len = header_len(&bytes);
+                assert_eq!(bytes.len(), 10 + len);
+                assert_eq!((10 + len) % 64, 0);
+                assert_eq!((10 + len) % 16, 0);
+                let header = std::str::from_utf8(&bytes[10..]).unwrap();
+                let dict_end = header.rfind('}').unwrap() + 1;
+                assert!(len - dict_end <= 64);
+                assert!(header[dict_end..len - 1].bytes().all(|b| b == b' '));
+            }
+        }
+    }
+
+    #[test]
+    fn one_dimensional_shape_has_trailing_comma() {
+        let mut bytes = vec![];
+        write_complex(&mut bytes, &[5], &[]).unwrap();
+        let header = std::str::from_utf8(&bytes[10..]).unwrap();
+        assert!(header.contains("'shape': (5,), "));
+    }
+
+    #[test]
+    fn data_round_trip() {
+        let data: Vec<Complex> = (0..4)
+            .map(|i| Complex::new(i as f32 * 0.5, -(i as f32) - 0.25))
+            .collect();
+        let mut bytes = vec![];
+        write_complex(&mut bytes, &[4], &data).unwrap();
+
+        let body = &bytes[10 + header_len(&bytes)..];
+        let read: Vec<Complex> = body
+            .chunks(8)
+            .map(|c| {
+                Complex::new(
+                    f32::from_le_bytes([c[0], c[1], c[2], c[3]]),
+                    f32::from_le_bytes([c[4], c[5], c[6], c[7]]),
+                )
+            })
+            .collect();
+        assert_eq!(read, data);
+    }
+}
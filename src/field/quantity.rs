@@ -0,0 +1,66 @@
+/*
+ * File: quantity.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use crate::field::Complex;
+
+/// Scalar derived from the complex pressure for display on a slice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldQuantity {
+    /// Absolute pressure `|p|`.
+    Amplitude,
+    /// Instantaneous pressure `Re(p)`.
+    RealPart,
+    /// `arg(p)` in [-PI, PI].
+    Phase,
+    /// `|p|^2`.
+    Intensity,
+    /// Sound pressure level `20 log10(|p| / reference)` in dB.
+    Spl { reference: f32 },
+    /// Gor'kov potential of `ViewerSettings::particle`, shown relative to zero.
+    /// It depends on the pressure gradient, so `evaluate` returns `None`;
+    /// use `calc_gorkov_potential` instead.
+    GorkovPotential,
+}
+
+impl FieldQuantity {
+    /// Value of this quantity for the pressure `p`,
+    /// or `None` if it cannot be derived from the pressure alone.
+    pub fn evaluate(&self, p: Complex) -> Option<f32> {
+        match self {
+            FieldQuantity::Amplitude => Some(p.norm()),
+            FieldQuantity::RealPart => Some(p.re),
+            FieldQuantity::Phase => Some(p.arg()),
+            FieldQuantity::Intensity => Some(p.norm_sqr()),
+            FieldQuantity::Spl { reference } => Some(20.0 * (p.norm() / reference).log10()),
+            FieldQuantity::GorkovPotential => None,
+        }
+    }
+
+    pub(crate) fn shader_id(&self) -> i32 {
+        match self {
+            FieldQuantity::Amplitude => 0,
+            FieldQuantity::RealPart => 1,
+            FieldQuantity::Phase => 2,
+            FieldQuantity::Intensity => 3,
+            FieldQuantity::Spl { .. } => 4,
+            FieldQuantity::GorkovPotential => 5,
+        }
+    }
+
+    pub(crate) fn spl_reference(&self) -> f32 {
+        match self {
+            FieldQuantity::Spl { reference } => *reference,
+            _ => 1.0,
+        }
+    }
+}
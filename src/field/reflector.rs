@@ -0,0 +1,170 @@
+/*
+ * File: reflector.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+
+/// Planar rigid boundary, taken into account through image sources.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reflector {
+    /// A point on the plane.
+    pub pos: Vector3,
+    pub normal: Vector3,
+    /// Pressure reflection coefficient, 1 for a perfectly rigid surface.
+    pub coefficient: f32,
+    /// Half width in mm of the square drawn in the 3D view.
+    /// The field computation treats the plane as infinite.
+    pub extent: f32,
+}
+
+impl Reflector {
+    pub fn new(pos: Vector3, normal: Vector3, coefficient: f32) -> Reflector {
+        Reflector {
+            pos,
+            normal: vecmath::vec3_normalized(normal),
+            coefficient,
+            extent: 150.,
+        }
+    }
+
+    /// Image of `source` mirrored in this plane, with its amplitude scaled by the coefficient.
+    pub fn mirror(&self, source: &SoundSource) -> SoundSource {
+        let n = self.normal;
+        let d = vecmath::vec3_dot(vecmath::vec3_sub(source.pos, self.pos), n);
+        let pos = vecmath::vec3_sub(source.pos, vecmath::vec3_scale(n, 2. * d));
+        let dir = vecmath::vec3_sub(
+            source.dir,
+            vecmath::vec3_scale(n, 2. * vecmath::vec3_dot(source.dir, n)),
+        );
+        SoundSource::with_amp(pos, dir, source.phase, source.amp * self.coefficient)
+    }
+}
+
+/// `sources` followed by their image sources up to `order` reflections.
+///
+/// The images also radiate into the half spaces behind the reflectors,
+/// where the field has no physical meaning.
+pub fn image_sources(
+    sources: &[SoundSource],
+    reflectors: &[Reflector],
+    order: usize,
+) -> Vec<SoundSource> {
+    let mut images = Vec::new();
+    for source in sources {
+        for_each_image(source, reflectors, order, &mut |image| images.push(*image));
+    }
+    images
+}
+
+/// Calls `f` on `source` and on each of its images up to `order` reflections.
+/// An image is never mirrored again in the plane that produced it.
+pub(crate) fn for_each_image<F: FnMut(&SoundSource)>(
+    source: &SoundSource,
+    reflectors: &[Reflector],
+    order: usize,
+    f: &mut F,
+) {
+    visit(source, reflectors, order, None, f);
+}
+
+fn visit<F: FnMut(&SoundSource)>(
+    source: &SoundSource,
+    reflectors: &[Reflector],
+    order: usize,
+    last: Option<usize>,
+    f: &mut F,
+) {
+    f(source);
+    if order == 0 {
+        return;
+    }
+    for (i, reflector) in reflectors.iter().enumerate() {
+        if Some(i) != last {
+            visit(&reflector.mirror(source), reflectors, order - 1, Some(i), f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::field::calc_pressure;
+    use crate::vec_utils::dist;
+    use crate::view::ViewerSettings;
+
+    fn assert_close(a: Vector3, b: Vector3) {
+        assert!(dist(a, b) < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn mirror_reflects_position_and_direction() {
+        let reflector = Reflector::new([0., 0., -50.], [0., 0., 2.], 0.8);
+        let dir = vecmath::vec3_normalized([0., 1., 1.]);
+        let source = SoundSource::with_amp([1., 2., 3.], dir, 0.3, 2.);
+
+        let image = reflector.mirror(&source);
+        assert_close(image.pos, [1., 2., -103.]);
+        assert_close(image.dir, [0., dir[1], -dir[2]]);
+        assert_eq!(image.phase, 0.3);
+        assert!((image.amp - 1.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn mirror_in_oblique_plane_is_an_involution() {
+        let reflector = Reflector::new([10., -5., 20.], [1., 2., -1.], 1.);
+        let source = SoundSource::with_amp([3., 4., 5.], [0., 0., 1.], 0., 1.);
+        let image = reflector.mirror(&source);
+        let n = reflector.normal;
+        let offset = |p| vecmath::vec3_dot(vecmath::vec3_sub(p, reflector.pos), n);
+        assert!((offset(image.pos) + offset(source.pos)).abs() < 1e-4);
+
+        let back = reflector.mirror(&image);
+        assert_close(back.pos, source.pos);
+        assert_close(back.dir, source.dir);
+    }
+
+    #[test]
+    fn image_sources_skip_the_producing_plane() {
+        let reflectors = [
+            Reflector::new([0., 0., -10.], [0., 0., 1.], 1.),
+            Reflector::new([-10., 0., 0.], [1., 0., 0.], 1.),
+        ];
+        let sources = [SoundSource::new([0.; 3], [0., 0., 1.], 0.); 2];
+        assert_eq!(image_sources(&sources, &reflectors, 0).len(), 2);
+        assert_eq!(image_sources(&sources, &reflectors, 1).len(), 2 * 3);
+        assert_eq!(image_sources(&sources, &reflectors, 2).len(), 2 * 5);
+
+        let images = image_sources(&sources[..1], &reflectors, 2);
+        assert_close(images[1].pos, [0., 0., -20.]);
+        assert_close(images[2].pos, [-20., 0., -20.]);
+        assert_close(images[3].pos, [-20., 0., 0.]);
+        assert_close(images[4].pos, [-20., 0., -20.]);
+    }
+
+    #[test]
+    fn rigid_wall_doubles_pressure_on_the_wall() {
+        let mut settings = ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        );
+        let source = [SoundSource::new([0., 0., 100.], [0., 0., -1.], 0.)];
+        let point = [30., 0., 0.];
+        let free = calc_pressure(point, &source, &settings);
+        settings.reflectors = vec![Reflector::new([0.; 3], [0., 0., 1.], 1.)];
+        let reflected = calc_pressure(point, &source, &settings);
+        assert!((reflected - free * 2.).norm() < 1e-4 * free.norm());
+    }
+}
@@ -0,0 +1,148 @@
+/*
+ * File: slice.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::field::{calc_field, npy, Complex};
+use crate::sound_source::SoundSource;
+use crate::vec_utils::{Matrix4, Vector3};
+use crate::view::ViewerSettings;
+
+/// Complex pressure sampled on a grid over a slice plane.
+///
+/// The plane is the unit quad `[-1, 1] x {0} x [-1, 1]` transformed by `model`,
+/// which is the geometry drawn by `AcousticFiledSliceViewer`.
+/// Samples are stored row by row, `width` samples along local x per row
+/// and `height` rows along local z.
+pub struct SliceField {
+    pub width: usize,
+    pub height: usize,
+    pub points: Vec<Vector3>,
+    pub pressures: Vec<Complex>,
+}
+
+impl SliceField {
+    pub fn sample(
+        model: Matrix4,
+        width: usize,
+        height: usize,
+        sources: &[SoundSource],
+        settings: &ViewerSettings,
+    ) -> SliceField {
+        let mut points = Vec::with_capacity(width * height);
+        for j in 0..height {
+            for i in 0..width {
+                let u = linspace(i, width);
+                let v = linspace(j, height);
+                let p = vecmath::col_mat4_transform(model, [u, 0., v, 1.]);
+                points.push([p[0], p[1], p[2]]);
+            }
+        }
+        let pressures = calc_field(&points, sources, settings);
+        SliceField {
+            width,
+            height,
+            points,
+            pressures,
+        }
+    }
+
+    /// Writes one line `x,y,z,re,im` per sample.
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "x,y,z,re,im")?;
+        for (p, c) in self.points.iter().zip(self.pressures.iter()) {
+            writeln!(writer, "{},{},{},{},{}", p[0], p[1], p[2], c.re, c.im)?;
+        }
+        writer.flush()
+    }
+
+    /// Writes the pressures as a complex64 array of shape `(height, width)`.
+    pub fn write_npy<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        npy::write_complex(&mut writer, &[self.height, self.width], &self.pressures)?;
+        writer.flush()
+    }
+}
+
+fn linspace(i: usize, n: usize) -> f32 {
+    if n < 2 {
+        0.
+    } else {
+        -1. + 2. * i as f32 / (n - 1) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::field::calc_pressure;
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    // 20 x 40 mm slice in the xz-plane centered at (0, 0, 100).
+    fn model() -> Matrix4 {
+        [
+            [10., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [0., 0., 20., 0.],
+            [0., 0., 100., 1.],
+        ]
+    }
+
+    #[test]
+    fn samples_cover_the_slice_row_by_row() {
+        let sources = [SoundSource::new([0., 0., 0.], [0., 0., 1.], 0.)];
+        let field = SliceField::sample(model(), 3, 2, &sources, &settings());
+        assert_eq!(field.width, 3);
+        assert_eq!(field.height, 2);
+        assert_eq!(field.points.len(), 6);
+        assert_eq!(field.points[0], [-10., 0., 80.]);
+        assert_eq!(field.points[1], [0., 0., 80.]);
+        assert_eq!(field.points[2], [10., 0., 80.]);
+        assert_eq!(field.points[3], [-10., 0., 120.]);
+        assert_eq!(field.points[5], [10., 0., 120.]);
+        for (p, c) in field.points.iter().zip(field.pressures.iter()) {
+            assert_eq!(*c, calc_pressure(*p, &sources, &settings()));
+        }
+    }
+
+    #[test]
+    fn single_sample_is_at_the_center() {
+        let field = SliceField::sample(model(), 1, 1, &[], &settings());
+        assert_eq!(field.points, vec![[0., 0., 100.]]);
+        assert_eq!(field.pressures, vec![Complex::new(0., 0.)]);
+    }
+
+    #[test]
+    fn npy_shape_is_height_by_width() {
+        let field = SliceField::sample(model(), 4, 3, &[], &settings());
+        let path = std::env::temp_dir().join("acoustic_field_viewer_slice_test.npy");
+        field.write_npy(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let header = String::from_utf8_lossy(&bytes[10..]);
+        assert!(header.contains("'shape': (3, 4), "));
+        assert_eq!(bytes.len() % 64, (12 * 8) % 64);
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ * File: volume.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::field::{calc_field, vtk, Complex};
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+use crate::view::ViewerSettings;
+
+/// Complex pressure sampled on a regular grid filling an axis-aligned box.
+///
+/// Samples are stored with x varying fastest, then y, then z.
+pub struct VolumeField {
+    pub origin: Vector3,
+    pub spacing: Vector3,
+    pub dims: [usize; 3],
+    pub pressures: Vec<Complex>,
+}
+
+impl VolumeField {
+    /// Samples the box spanned by `min` and `max` (both included) with `dims` points per axis.
+    pub fn sample(
+        min: Vector3,
+        max: Vector3,
+        dims: [usize; 3],
+        sources: &[SoundSource],
+        settings: &ViewerSettings,
+    ) -> VolumeField {
+        let mut spacing = [0.; 3];
+        for axis in 0..3 {
+            if dims[axis] > 1 {
+                spacing[axis] = (max[axis] - min[axis]) / (dims[axis] - 1) as f32;
+            }
+        }
+        let mut points = Vec::with_capacity(dims[0] * dims[1] * dims[2]);
+        for k in 0..dims[2] {
+            for j in 0..dims[1] {
+                for i in 0..dims[0] {
+                    points.push([
+                        min[0] + spacing[0] * i as f32,
+                        min[1] + spacing[1] * j as f32,
+                        min[2] + spacing[2] * k as f32,
+                    ]);
+                }
+            }
+        }
+        let pressures = calc_field(&points, sources, settings);
+        VolumeField {
+            origin: min,
+            spacing,
+            dims,
+            pressures,
+        }
+    }
+
+    /// Writes the amplitude and phase as legacy VTK structured points, readable by ParaView.
+    pub fn write_vtk<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        vtk::write_structured_points(
+            &mut writer,
+            self.origin,
+            self.spacing,
+            self.dims,
+            &[
+                (
+                    "amplitude",
+                    self.pressures.iter().map(|p| p.norm()).collect(),
+                ),
+                ("phase", self.pressures.iter().map(|p| p.arg()).collect()),
+            ],
+        )?;
+        writer.flush()
+    }
+}
+
+/// Writes transducer positions as a legacy VTK point cloud with `direction`, `phase` and `amp` attributes.
+pub fn write_sources_vtk<P: AsRef<Path>>(path: P, sources: &[SoundSource]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    vtk::write_point_cloud(&mut writer, sources)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::field::calc_pressure;
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    #[test]
+    fn samples_with_x_fastest() {
+        let sources = [SoundSource::new([0., 0., 0.], [0., 0., 1.], 0.)];
+        let field = VolumeField::sample(
+            [-10., 0., 50.],
+            [10., 5., 60.],
+            [3, 2, 2],
+            &sources,
+            &settings(),
+        );
+        assert_eq!(field.spacing, [10., 5., 10.]);
+        assert_eq!(field.pressures.len(), 12);
+        // Index i + 3 (j + 2 k) is the point (i, j, k).
+        let point = [0., 5., 60.];
+        assert_eq!(
+            field.pressures[1 + 3 * (1 + 2)],
+            calc_pressure(point, &sources, &settings())
+        );
+    }
+
+    #[test]
+    fn flat_axis_has_zero_spacing() {
+        let field = VolumeField::sample([0.; 3], [10., 10., 10.], [2, 1, 3], &[], &settings());
+        assert_eq!(field.spacing, [10., 0., 5.]);
+        assert_eq!(field.pressures.len(), 6);
+    }
+
+    #[test]
+    fn vtk_has_one_value_per_point() {
+        let field = VolumeField::sample([0.; 3], [4., 4., 4.], [3, 2, 2], &[], &settings());
+        let path = std::env::temp_dir().join("acoustic_field_viewer_volume_test.vtk");
+        field.write_vtk(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[4], "DIMENSIONS 3 2 2");
+        assert_eq!(lines[7], "POINT_DATA 12");
+        assert_eq!(lines.len(), 8 + 2 * (2 + 12));
+    }
+}
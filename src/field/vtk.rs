@@ -0,0 +1,148 @@
+/*
+ * File: vtk.rs
+ * Project: field
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::io::{self, Write};
+
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+
+const HEADER: &str = "# vtk DataFile Version 3.0\nacoustic_field_viewer\nASCII\n";
+
+pub(crate) fn write_structured_points<W: Write>(
+    writer: &mut W,
+    origin: Vector3,
+    spacing: Vector3,
+    dims: [usize; 3],
+    scalars: &[(&str, Vec<f32>)],
+) -> io::Result<()> {
+    write!(writer, "{}", HEADER)?;
+    writeln!(writer, "DATASET STRUCTURED_POINTS")?;
+    writeln!(writer, "DIMENSIONS {} {} {}", dims[0], dims[1], dims[2])?;
+    writeln!(writer, "ORIGIN {} {} {}", origin[0], origin[1], origin[2])?;
+    writeln!(
+        writer,
+        "SPACING {} {} {}",
+        spacing[0], spacing[1], spacing[2]
+    )?;
+    writeln!(writer, "POINT_DATA {}", dims[0] * dims[1] * dims[2])?;
+    for (name, values) in scalars {
+        write_scalars(writer, name, values.iter().copied())?;
+    }
+    Ok(())
+}
+
+pub(crate) fn write_point_cloud<W: Write>(
+    writer: &mut W,
+    sources: &[SoundSource],
+) -> io::Result<()> {
+    let n = sources.len();
+    write!(writer, "{}", HEADER)?;
+    writeln!(writer, "DATASET POLYDATA")?;
+    writeln!(writer, "POINTS {} float", n)?;
+    for source in sources {
+        writeln!(
+            writer,
+            "{} {} {}",
+            source.pos[0], source.pos[1], source.pos[2]
+        )?;
+    }
+    writeln!(writer, "VERTICES {} {}", n, 2 * n)?;
+    for i in 0..n {
+        writeln!(writer, "1 {}", i)?;
+    }
+    writeln!(writer, "POINT_DATA {}", n)?;
+    writeln!(writer, "VECTORS direction float")?;
+    for source in sources {
+        writeln!(
+            writer,
+            "{} {} {}",
+            source.dir[0], source.dir[1], source.dir[2]
+        )?;
+    }
+    write_scalars(writer, "phase", sources.iter().map(|s| s.phase))?;
+    write_scalars(writer, "amp", sources.iter().map(|s| s.amp))
+}
+
+fn write_scalars<W: Write, I: Iterator<Item = f32>>(
+    writer: &mut W,
+    name: &str,
+    values: I,
+) -> io::Result<()> {
+    writeln!(writer, "SCALARS {} float 1", name)?;
+    writeln!(writer, "LOOKUP_TABLE default")?;
+    for v in values {
+        writeln!(writer, "{}", v)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structured_points_header() {
+        let mut bytes = vec![];
+        write_structured_points(
+            &mut bytes,
+            [-1., 0., 2.5],
+            [0.5, 1., 2.],
+            [2, 3, 1],
+            &[("amplitude", vec![0.; 6]), ("phase", vec![1.; 6])],
+        )
+        .unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            &lines[..8],
+            &[
+                "# vtk DataFile Version 3.0",
+                "acoustic_field_viewer",
+                "ASCII",
+                "DATASET STRUCTURED_POINTS",
+                "DIMENSIONS 2 3 1",
+                "ORIGIN -1 0 2.5",
+                "SPACING 0.5 1 2",
+                "POINT_DATA 6",
+            ]
+        );
+        assert_eq!(lines[8], "SCALARS amplitude float 1");
+        assert_eq!(lines[9], "LOOKUP_TABLE default");
+        assert_eq!(lines[16], "SCALARS phase float 1");
+        assert_eq!(lines.len(), 8 + 2 * (2 + 6));
+    }
+
+    #[test]
+    fn point_cloud_counts() {
+        let sources = [
+            SoundSource::with_amp([1., 2., 3.], [0., 0., 1.], 0.5, 1.),
+            SoundSource::with_amp([4., 5., 6.], [0., 1., 0.], 1.5, 0.5),
+        ];
+        let mut bytes = vec![];
+        write_point_cloud(&mut bytes, &sources).unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[3], "DATASET POLYDATA");
+        assert_eq!(lines[4], "POINTS 2 float");
+        assert_eq!(lines[5], "1 2 3");
+        assert_eq!(lines[7], "VERTICES 2 4");
+        assert_eq!(lines[9], "1 1");
+        assert_eq!(lines[10], "POINT_DATA 2");
+        assert_eq!(lines[11], "VECTORS direction float");
+        assert_eq!(lines[13], "0 1 0");
+        assert_eq!(
+            &lines[lines.len() - 4..],
+            &["SCALARS amp float 1", "LOOKUP_TABLE default", "1", "0.5"]
+        );
+    }
+}
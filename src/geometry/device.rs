@@ -0,0 +1,126 @@
+/*
+ * File: device.rs
+ * Project: geometry
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use crate::sound_source::SoundSource;
+use crate::vec_utils;
+use crate::vec_utils::{Matrix4, Quaterion, Vector3};
+
+/// A group of sources moved as a rigid body.
+///
+/// The sources are kept in the device's local frame; their world positions are
+/// obtained by rotating with `rotation` and then translating by `translation`.
+#[derive(Debug, Clone)]
+pub struct Device {
+    local: Vec<SoundSource>,
+    pub translation: Vector3,
+    pub rotation: Quaterion,
+}
+
+impl Device {
+    pub fn new(local: Vec<SoundSource>, translation: Vector3, rotation: Quaterion) -> Device {
+        Device {
+            local,
+            translation,
+            rotation,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.local.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.local.is_empty()
+    }
+
+    pub fn model(&self) -> Matrix4 {
+        vecmath::col_mat4_mul(
+            vec_utils::mat4_t(self.translation),
+            vec_utils::mat4_rot(self.rotation),
+        )
+    }
+
+    /// Sources in world coordinates.
+    pub fn sources(&self) -> Vec<SoundSource> {
+        let mut sources = self.local.clone();
+        self.apply_pose(&mut sources);
+        sources
+    }
+
+    /// Overwrites the positions and directions of `sources` with the posed local ones.
+    /// Phases and amplitudes are left untouched.
+    pub(crate) fn apply_pose(&self, sources: &mut [SoundSource]) {
+        let model = self.model();
+        for (source, local) in sources.iter_mut().zip(self.local.iter()) {
+            let p = vecmath::col_mat4_transform(model, vec_utils::to_point4(local.pos));
+            let d = vecmath::col_mat4_transform(model, vec_utils::to_vec4(local.dir));
+            source.pos = [p[0], p[1], p[2]];
+            source.dir = [d[0], d[1], d[2]];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec_utils::dist;
+    use std::f32::consts::PI;
+
+    fn assert_close(a: Vector3, b: Vector3) {
+        assert!(dist(a, b) < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    fn local() -> Vec<SoundSource> {
+        vec![
+            SoundSource::with_amp([10., 0., 0.], [1., 0., 0.], 0.5, 0.8),
+            SoundSource::with_amp([0., 0., 5.], [0., 0., 1.], 1.5, 1.0),
+        ]
+    }
+
+    #[test]
+    fn rotates_then_translates() {
+        let rotation = quaternion::axis_angle([0., 0., 1.], PI / 2.);
+        let device = Device::new(local(), [100., 200., 300.], rotation);
+        let sources = device.sources();
+        assert_eq!(device.len(), 2);
+        assert_close(sources[0].pos, [100., 210., 300.]);
+        assert_close(sources[0].dir, [0., 1., 0.]);
+        assert_close(sources[1].pos, [100., 200., 305.]);
+        assert_close(sources[1].dir, [0., 0., 1.]);
+        assert_eq!(sources[0].phase, 0.5);
+        assert_eq!(sources[0].amp, 0.8);
+    }
+
+    #[test]
+    fn model_matches_quaternion_rotation() {
+        let rotation = quaternion::axis_angle(vecmath::vec3_normalized([1., -2., 0.5]), 0.7);
+        let translation = [3., -4., 5.];
+        let device = Device::new(local(), translation, rotation);
+        for (world, local) in device.sources().iter().zip(local().iter()) {
+            let expected =
+                vecmath::vec3_add(quaternion::rotate_vector(rotation, local.pos), translation);
+            assert_close(world.pos, expected);
+            assert_close(world.dir, quaternion::rotate_vector(rotation, local.dir));
+        }
+    }
+
+    #[test]
+    fn apply_pose_keeps_phase_and_amplitude() {
+        let device = Device::new(local(), [0., 0., 10.], (1., [0.; 3]));
+        let mut sources = vec![SoundSource::with_amp([0.; 3], [1., 0., 0.], 2.5, 0.3); 2];
+        device.apply_pose(&mut sources);
+        assert_close(sources[0].pos, [10., 0., 10.]);
+        assert_close(sources[1].pos, [0., 0., 15.]);
+        assert!(sources.iter().all(|s| s.phase == 2.5 && s.amp == 0.3));
+    }
+}
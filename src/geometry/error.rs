@@ -0,0 +1,61 @@
+/*
+ * File: error.rs
+ * Project: geometry
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum GeometryError {
+    Io(io::Error),
+    UnsupportedFormat(String),
+    Parse {
+        line: usize,
+        message: String,
+    },
+    Json(serde_json::Error),
+    /// An array generator was given a parameter outside its valid range.
+    InvalidParameter(String),
+}
+
+impl fmt::Display for GeometryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeometryError::Io(e) => write!(f, "failed to read geometry: {}", e),
+            GeometryError::UnsupportedFormat(ext) => write!(
+                f,
+                "unsupported geometry format '{}', expected .csv or .json",
+                ext
+            ),
+            GeometryError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            GeometryError::Json(e) => write!(f, "{}", e),
+            GeometryError::InvalidParameter(message) => {
+                write!(f, "invalid array parameter: {}", message)
+            }
+        }
+    }
+}
+
+impl Error for GeometryError {}
+
+impl From<io::Error> for GeometryError {
+    fn from(e: io::Error) -> Self {
+        GeometryError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for GeometryError {
+    fn from(e: serde_json::Error) -> Self {
+        GeometryError::Json(e)
+    }
+}
@@ -0,0 +1,306 @@
+/*
+ * File: generator.rs
+ * Project: geometry
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+//! Array generators. Unless noted otherwise, arrays lie in the xy-plane
+//! and face +z, with unit amplitude and zero phase.
+//! Lengths and counts are checked up front and rejected with `GeometryError::InvalidParameter`.
+
+use std::f32::consts::PI;
+
+use crate::geometry::{Device, GeometryError};
+use crate::sound_source::SoundSource;
+use crate::vec_utils::{Quaterion, Vector3};
+
+pub const AUTD_NUM_TRANS_X: usize = 18;
+pub const AUTD_NUM_TRANS_Y: usize = 14;
+pub const AUTD_TRANS_SIZE: f32 = 10.18;
+/// Grid indices `(x, y)` left empty for the screw holes of an AUTD board.
+pub const AUTD_MISSING: [(usize, usize); 3] = [(1, 1), (2, 1), (16, 1)];
+
+const ZDIR: Vector3 = [0., 0., 1.];
+
+/// `nx` x `ny` grid with spacing `pitch`, starting at the origin.
+/// Elements whose `(x, y)` index is listed in `missing` are skipped.
+pub fn rect_grid(
+    nx: usize,
+    ny: usize,
+    pitch: f32,
+    missing: &[(usize, usize)],
+) -> Result<Vec<SoundSource>, GeometryError> {
+    check_positive("pitch", pitch)?;
+    let mut sources = Vec::with_capacity(nx * ny);
+    for y in 0..ny {
+        for x in 0..nx {
+            if missing.contains(&(x, y)) {
+                continue;
+            }
+            let pos = [pitch * x as f32, pitch * y as f32, 0.];
+            sources.push(SoundSource::new(pos, ZDIR, 0.));
+        }
+    }
+    Ok(sources)
+}
+
+/// A single AUTD board: 18 x 14 grid without the elements at the screw holes.
+pub fn autd_device() -> Vec<SoundSource> {
+    rect_grid(
+        AUTD_NUM_TRANS_X,
+        AUTD_NUM_TRANS_Y,
+        AUTD_TRANS_SIZE,
+        &AUTD_MISSING,
+    )
+    .unwrap()
+}
+
+/// Hexagonally packed grid of `ny` rows with `nx` elements each.
+/// Odd rows are shifted by half a pitch.
+pub fn hex_grid(nx: usize, ny: usize, pitch: f32) -> Result<Vec<SoundSource>, GeometryError> {
+    check_positive("pitch", pitch)?;
+    let row_pitch = pitch * 3f32.sqrt() / 2.;
+    let mut sources = Vec::with_capacity(nx * ny);
+    for y in 0..ny {
+        let offset = if y % 2 == 1 { pitch / 2. } else { 0. };
+        for x in 0..nx {
+            let pos = [offset + pitch * x as f32, row_pitch * y as f32, 0.];
+            sources.push(SoundSource::new(pos, ZDIR, 0.));
+        }
+    }
+    Ok(sources)
+}
+
+/// `count` elements evenly spaced on a circle of `radius` around the origin.
+pub fn circular_array(radius: f32, count: usize) -> Result<Vec<SoundSource>, GeometryError> {
+    check_non_negative("radius", radius)?;
+    if count == 0 {
+        return Err(GeometryError::InvalidParameter(
+            "count must be at least 1".to_string(),
+        ));
+    }
+    Ok(ring(radius, count))
+}
+
+/// Concentric rings from `inner` to `outer` radius, `pitch` apart both radially and along each ring.
+pub fn annular_array(
+    inner: f32,
+    outer: f32,
+    pitch: f32,
+) -> Result<Vec<SoundSource>, GeometryError> {
+    check_non_negative("inner radius", inner)?;
+    check_non_negative("outer radius", outer)?;
+    check_positive("pitch", pitch)?;
+    if inner > outer {
+        return Err(GeometryError::InvalidParameter(format!(
+            "inner radius {} is larger than outer radius {}",
+            inner, outer
+        )));
+    }
+    let rings = ((outer - inner) / pitch).floor() as usize;
+    let mut sources = Vec::new();
+    for i in 0..=rings {
+        let radius = inner + pitch * i as f32;
+        sources.extend(ring(radius, ring_count(radius, pitch)));
+    }
+    Ok(sources)
+}
+
+/// Elements on a spherical cap of curvature `radius` whose apex is at the origin.
+/// All elements face the center of the sphere at `(0, 0, radius)`.
+/// `aperture` is the half opening angle of the cap in radians, at most PI.
+pub fn spherical_cap(
+    radius: f32,
+    aperture: f32,
+    pitch: f32,
+) -> Result<Vec<SoundSource>, GeometryError> {
+    check_positive("radius", radius)?;
+    check_positive("pitch", pitch)?;
+    check_non_negative("aperture", aperture)?;
+    if aperture > PI {
+        return Err(GeometryError::InvalidParameter(format!(
+            "aperture must be at most PI, got {}",
+            aperture
+        )));
+    }
+    let center = [0., 0., radius];
+    let mut sources = Vec::new();
+    let rings = (aperture * radius / pitch).floor() as usize;
+    for i in 0..=rings {
+        let theta = i as f32 * pitch / radius;
+        for s in ring(
+            radius * theta.sin(),
+            ring_count(radius * theta.sin(), pitch),
+        ) {
+            let pos = [s.pos[0], s.pos[1], radius * (1. - theta.cos())];
+            let dir = vecmath::vec3_normalized(vecmath::vec3_sub(center, pos));
+            sources.push(SoundSource::new(pos, dir, 0.));
+        }
+    }
+    Ok(sources)
+}
+
+/// Copies of `device`, each rotated by `rotation` and then moved by `translation`.
+pub fn multi_device(device: &[SoundSource], poses: &[(Vector3, Quaterion)]) -> Vec<SoundSource> {
+    poses
+        .iter()
+        .flat_map(|&(translation, rotation)| place(device, translation, rotation))
+        .collect()
+}
+
+/// Rotates `sources` about the origin by `rotation` and then moves them by `translation`.
+pub fn place(
+    sources: &[SoundSource],
+    translation: Vector3,
+    rotation: Quaterion,
+) -> Vec<SoundSource> {
+    Device::new(sources.to_vec(), translation, rotation).sources()
+}
+
+fn ring(radius: f32, count: usize) -> Vec<SoundSource> {
+    (0..count)
+        .map(|i| {
+            let t = 2. * PI * i as f32 / count as f32;
+            SoundSource::new([radius * t.cos(), radius * t.sin(), 0.], ZDIR, 0.)
+        })
+        .collect()
+}
+
+fn ring_count(radius: f32, pitch: f32) -> usize {
+    ((2. * PI * radius / pitch).floor() as usize).max(1)
+}
+
+fn check_positive(name: &str, value: f32) -> Result<(), GeometryError> {
+    if value.is_finite() && value > 0. {
+        Ok(())
+    } else {
+        Err(GeometryError::InvalidParameter(format!(
+            "{} must be positive, got {}",
+            name, value
+        )))
+    }
+}
+
+fn check_non_negative(name: &str, value: f32) -> Result<(), GeometryError> {
+    if value.is_finite() && value >= 0. {
+        Ok(())
+    } else {
+        Err(GeometryError::InvalidParameter(format!(
+            "{} must not be negative, got {}",
+            name, value
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vec_utils::dist;
+
+    fn assert_close(a: Vector3, b: Vector3) {
+        assert!(dist(a, b) < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn rect_grid_skips_missing() {
+        let sources = rect_grid(3, 2, 10., &[(1, 0)]).unwrap();
+        assert_eq!(sources.len(), 5);
+        assert_close(sources[0].pos, [0., 0., 0.]);
+        assert_close(sources[1].pos, [20., 0., 0.]);
+        assert_close(sources[4].pos, [20., 10., 0.]);
+        assert!(sources.iter().all(|s| s.dir == ZDIR));
+    }
+
+    #[test]
+    fn autd_device_has_249_elements() {
+        let sources = autd_device();
+        assert_eq!(sources.len(), AUTD_NUM_TRANS_X * AUTD_NUM_TRANS_Y - 3);
+        // (1, 1) and (2, 1) are screw holes, so the second element of row 1 is at x = 3.
+        assert_close(
+            sources[AUTD_NUM_TRANS_X + 1].pos,
+            [3. * AUTD_TRANS_SIZE, AUTD_TRANS_SIZE, 0.],
+        );
+    }
+
+    #[test]
+    fn hex_grid_shifts_odd_rows() {
+        let sources = hex_grid(4, 3, 10.).unwrap();
+        assert_eq!(sources.len(), 12);
+        assert_close(sources[3].pos, [30., 0., 0.]);
+        assert_close(sources[4].pos, [5., 10. * 3f32.sqrt() / 2., 0.]);
+        assert_close(sources[8].pos, [0., 10. * 3f32.sqrt(), 0.]);
+    }
+
+    #[test]
+    fn circular_array_is_evenly_spaced() {
+        let sources = circular_array(50., 8).unwrap();
+        assert_eq!(sources.len(), 8);
+        assert_close(sources[0].pos, [50., 0., 0.]);
+        assert_close(sources[2].pos, [0., 50., 0.]);
+        assert!(sources
+            .iter()
+            .all(|s| (dist(s.pos, [0.; 3]) - 50.).abs() < 1e-3));
+    }
+
+    #[test]
+    fn annular_array_rings() {
+        // One element at the center, then floor(2 PI r / pitch) per ring.
+        let sources = annular_array(0., 20., 10.).unwrap();
+        assert_eq!(sources.len(), 1 + 6 + 12);
+        assert_close(sources[0].pos, [0., 0., 0.]);
+        assert_close(sources[1].pos, [10., 0., 0.]);
+        assert_close(sources[7].pos, [20., 0., 0.]);
+    }
+
+    #[test]
+    fn spherical_cap_faces_center() {
+        let sources = spherical_cap(100., 0.3, 10.).unwrap();
+        // Rings at theta = 0, 0.1, 0.2 and 0.3 rad.
+        assert_eq!(sources.len(), 1 + 6 + 12 + 18);
+        assert_close(sources[0].pos, [0., 0., 0.]);
+        for s in &sources {
+            assert!((dist(s.pos, [0., 0., 100.]) - 100.).abs() < 1e-2);
+            let to_center = vecmath::vec3_normalized(vecmath::vec3_sub([0., 0., 100.], s.pos));
+            assert_close(s.dir, to_center);
+        }
+    }
+
+    #[test]
+    fn multi_device_places_copies() {
+        let device = rect_grid(2, 1, 10., &[]).unwrap();
+        let quarter = ((PI / 4.).cos(), [0., 0., (PI / 4.).sin()]);
+        let sources = multi_device(
+            &device,
+            &[([0.; 3], (1., [0.; 3])), ([0., 0., 50.], quarter)],
+        );
+        assert_eq!(sources.len(), 4);
+        assert_close(sources[1].pos, [10., 0., 0.]);
+        assert_close(sources[2].pos, [0., 0., 50.]);
+        assert_close(sources[3].pos, [0., 10., 50.]);
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        let invalid = |r: Result<Vec<SoundSource>, GeometryError>| match r {
+            Err(GeometryError::InvalidParameter(_)) => {}
+            r => panic!("expected InvalidParameter, got {:?}", r.map(|s| s.len())),
+        };
+        invalid(rect_grid(2, 2, 0., &[]));
+        invalid(hex_grid(2, 2, f32::NAN));
+        invalid(circular_array(10., 0));
+        invalid(circular_array(-1., 4));
+        invalid(annular_array(0., 20., 0.));
+        invalid(annular_array(0., 20., -1.));
+        invalid(annular_array(30., 20., 10.));
+        invalid(spherical_cap(100., 0.3, 0.));
+        invalid(spherical_cap(0., 0.3, 10.));
+        invalid(spherical_cap(100., 4., 10.));
+    }
+}
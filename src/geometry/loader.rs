@@ -0,0 +1,314 @@
+/*
+ * File: loader.rs
+ * Project: geometry
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+//! Transducer array files.
+//!
+//! CSV: one source per line as `x,y,z,dx,dy,dz[,phase[,amp]]`.
+//! `phase` defaults to 0 and `amp` to 1. Blank lines and lines starting with `#`
+//! are skipped, and so is a first line naming exactly those columns, e.g. `x,y,z,dx,dy,dz,phase`.
+//!
+//! JSON: an array of objects
+//! `{"pos": [x, y, z], "dir": [dx, dy, dz], "phase": 0.0, "amp": 1.0}`,
+//! where `phase` and `amp` are optional.
+//!
+//! Positions are in mm and phases in radians. Directions must be non-zero
+//! and amplitudes non-negative.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::geometry::GeometryError;
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+
+const CSV_COLUMNS: [&str; 8] = ["x", "y", "z", "dx", "dy", "dz", "phase", "amp"];
+
+/// Loads sources from a `.csv` or `.json` file, chosen by its extension.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<SoundSource>, GeometryError> {
+    let path = path.as_ref();
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+    let text = match ext.as_str() {
+        "csv" | "json" => fs::read_to_string(path)?,
+        _ => return Err(GeometryError::UnsupportedFormat(ext)),
+    };
+    if ext == "csv" {
+        parse_csv(&text)
+    } else {
+        parse_json(&text)
+    }
+}
+
+pub fn parse_csv(text: &str) -> Result<Vec<SoundSource>, GeometryError> {
+    let mut sources = Vec::new();
+    let mut first = true;
+    for (i, line) in text.lines().enumerate() {
+        let line_num = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let is_first = first;
+        first = false;
+        if is_first && is_csv_header(line) {
+            continue;
+        }
+        let error = |message: String| GeometryError::Parse {
+            line: line_num,
+            message,
+        };
+
+        let values = line
+            .split(',')
+            .map(|v| {
+                v.trim().parse::<f32>().map_err(|_| {
+                    if is_first {
+                        error(format!(
+                            "'{}' is neither a source nor the header x,y,z,dx,dy,dz[,phase[,amp]]",
+                            line
+                        ))
+                    } else {
+                        error(format!("'{}' is not a number", v.trim()))
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.len() < 6 || values.len() > 8 {
+            return Err(error(format!(
+                "expected 6 to 8 columns (x,y,z,dx,dy,dz[,phase[,amp]]), found {}",
+                values.len()
+            )));
+        }
+        let raw = RawSource {
+            pos: [values[0], values[1], values[2]],
+            dir: [values[3], values[4], values[5]],
+            phase: values.get(6).copied().unwrap_or(0.),
+            amp: values.get(7).copied().unwrap_or(1.),
+        };
+        sources.push(raw.validate().map_err(error)?);
+    }
+    Ok(sources)
+}
+
+fn is_csv_header(line: &str) -> bool {
+    let names: Vec<&str> = line.split(',').map(str::trim).collect();
+    (6..=CSV_COLUMNS.len()).contains(&names.len()) && names[..] == CSV_COLUMNS[..names.len()]
+}
+
+pub fn parse_json(text: &str) -> Result<Vec<SoundSource>, GeometryError> {
+    let raw: Vec<RawSource> = serde_json::from_str(text)?;
+    let mut sources = Vec::with_capacity(raw.len());
+    for (i, raw) in raw.into_iter().enumerate() {
+        match raw.validate() {
+            Ok(source) => sources.push(source),
+            Err(message) => {
+                let line = element_lines(text).get(i).copied().unwrap_or(0);
+                return Err(GeometryError::Parse { line, message });
+            }
+        }
+    }
+    Ok(sources)
+}
+
+/// Line on which each element of the top-level array starts, for text that is valid JSON.
+// serde_json reports errors raised after an object is read at the next token,
+// which may be on a later line, so the lines are looked up here instead.
+fn element_lines(text: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut line = 1;
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '\n' => line += 1,
+            '"' => in_string = true,
+            '{' | '[' => {
+                if depth == 1 {
+                    lines.push(line);
+                }
+                depth += 1;
+            }
+            '}' | ']' => depth -= 1,
+            _ => (),
+        }
+    }
+    lines
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSource {
+    pos: Vector3,
+    dir: Vector3,
+    #[serde(default)]
+    phase: f32,
+    #[serde(default = "default_amp")]
+    amp: f32,
+}
+
+fn default_amp() -> f32 {
+    1.0
+}
+
+impl RawSource {
+    fn validate(self) -> Result<SoundSource, String> {
+        let scalars = [self.phase, self.amp];
+        let mut values = self.pos.iter().chain(self.dir.iter()).chain(scalars.iter());
+        if values.any(|v| !v.is_finite()) {
+            return Err("values must be finite".to_string());
+        }
+        if self.dir.iter().all(|&d| d == 0.) {
+            return Err("direction must be non-zero".to_string());
+        }
+        if self.amp < 0. {
+            return Err(format!(
+                "amplitude must be non-negative, found {}",
+                self.amp
+            ));
+        }
+        Ok(SoundSource::with_amp(
+            self.pos, self.dir, self.phase, self.amp,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv_error_line(text: &str) -> usize {
+        match parse_csv(text) {
+            Err(GeometryError::Parse { line, .. }) => line,
+            other => panic!("expected a parse error, got {:?}", other.map(|s| s.len())),
+        }
+    }
+
+    fn json_error_line(text: &str) -> usize {
+        match parse_json(text) {
+            Err(GeometryError::Json(e)) => e.line(),
+            Err(GeometryError::Parse { line, .. }) => line,
+            other => panic!("expected a JSON error, got {:?}", other.map(|s| s.len())),
+        }
+    }
+
+    #[test]
+    fn csv_skips_header_comments_and_blank_lines() {
+        let text = "x,y,z,dx,dy,dz,phase,amp\n# comment\n\n0,0,0,0,0,1\n  \n10,0,0,0,0,1,0.5,0.8\n";
+        let sources = parse_csv(text).unwrap();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[1].pos, [10., 0., 0.]);
+    }
+
+    #[test]
+    fn csv_accepts_headers_with_optional_columns() {
+        for header in &[
+            "x,y,z,dx,dy,dz",
+            "x, y, z, dx, dy, dz, phase",
+            " x,y,z,dx,dy,dz,phase,amp ",
+        ] {
+            let text = format!("# array\n{}\n0,0,0,0,0,1\n", header);
+            assert_eq!(parse_csv(&text).unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn csv_rejects_unknown_header() {
+        assert_eq!(csv_error_line("x,y,z,nx,ny,nz\n0,0,0,0,0,1\n"), 1);
+        assert_eq!(csv_error_line("# array\n\nxpos,ypos,zpos,dx,dy,dz\n"), 3);
+        assert_eq!(csv_error_line("x,y,z,dx,dy,dz,phase,amp,extra\n"), 1);
+        // Only the first line may be a header.
+        assert_eq!(csv_error_line("0,0,0,0,0,1\nx,y,z,dx,dy,dz\n"), 2);
+        assert_eq!(csv_error_line("x,y,z,dx,dy,dz\nx,y,z,dx,dy,dz\n"), 2);
+    }
+
+    #[test]
+    fn csv_optional_columns_default() {
+        let sources = parse_csv("0,0,0,0,0,1\n0,0,0,0,0,1,1.5\n0,0,0,0,0,1,1.5,0.25").unwrap();
+        assert_eq!((sources[0].phase, sources[0].amp), (0., 1.));
+        assert_eq!((sources[1].phase, sources[1].amp), (1.5, 1.));
+        assert_eq!((sources[2].phase, sources[2].amp), (1.5, 0.25));
+    }
+
+    #[test]
+    fn csv_reports_bad_number_line() {
+        assert_eq!(csv_error_line("0,0,0,0,0,1\n# comment\n0,0,abc,0,0,1\n"), 3);
+    }
+
+    #[test]
+    fn csv_reports_wrong_column_count() {
+        assert_eq!(csv_error_line("0,0,0,0,0,1\n0,0,0,0,1\n"), 2);
+        assert_eq!(csv_error_line("0,0,0,0,0,1,0,1,2\n"), 1);
+    }
+
+    #[test]
+    fn csv_rejects_zero_direction() {
+        assert_eq!(csv_error_line("0,0,0,0,0,1\n\n0,0,0,0,0,0\n"), 3);
+    }
+
+    #[test]
+    fn csv_rejects_negative_amplitude() {
+        assert_eq!(csv_error_line("0,0,0,0,0,1,0,-1\n"), 1);
+    }
+
+    #[test]
+    fn json_optional_fields_default() {
+        let text = r#"[
+            {"pos": [0, 0, 0], "dir": [0, 0, 1]},
+            {"pos": [1, 2, 3], "dir": [0, 0, 1], "phase": 1.5, "amp": 0.25}
+        ]"#;
+        let sources = parse_json(text).unwrap();
+        assert_eq!((sources[0].phase, sources[0].amp), (0., 1.));
+        assert_eq!(sources[1].pos, [1., 2., 3.]);
+        assert_eq!((sources[1].phase, sources[1].amp), (1.5, 0.25));
+    }
+
+    #[test]
+    fn json_reports_bad_number_line() {
+        let text = "[\n{\"pos\": [0, 0, 0], \"dir\": [0, 0, 1]},\n{\"pos\": [0, \"a\", 0], \"dir\": [0, 0, 1]}\n]";
+        assert_eq!(json_error_line(text), 3);
+    }
+
+    #[test]
+    fn json_reports_wrong_vector_length() {
+        let text = "[\n{\"pos\": [0, 0], \"dir\": [0, 0, 1]}\n]";
+        assert_eq!(json_error_line(text), 2);
+    }
+
+    #[test]
+    fn json_rejects_zero_direction() {
+        let text = "[\n{\"pos\": [0, 0, 0], \"dir\": [0, 0, 1]},\n{\"pos\": [0, 0, 0], \"dir\": [0, 0, 0]}\n]";
+        assert_eq!(json_error_line(text), 3);
+    }
+
+    #[test]
+    fn json_rejects_negative_amplitude() {
+        let text = "[\n{\"pos\": [0, 0, 0], \"dir\": [0, 0, 1], \"amp\": -1}\n]";
+        assert_eq!(json_error_line(text), 2);
+    }
+}
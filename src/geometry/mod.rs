@@ -0,0 +1,25 @@
+/*
+ * File: mod.rs
+ * Project: geometry
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+mod device;
+mod error;
+mod generator;
+mod loader;
+
+pub use device::Device;
+pub use error::GeometryError;
+pub use generator::{
+    annular_array, autd_device, circular_array, hex_grid, multi_device, place, rect_grid,
+    spherical_cap, AUTD_MISSING, AUTD_NUM_TRANS_X, AUTD_NUM_TRANS_Y, AUTD_TRANS_SIZE,
+};
+pub use loader::{load, parse_csv, parse_json};
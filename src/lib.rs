@@ -6,11 +6,19 @@ extern crate gfx;
 extern crate conrod;
 extern crate conrod_core;
 extern crate gfx_device_gl;
+extern crate glutin;
+extern crate image;
+extern crate num_complex;
 extern crate quaternion;
 extern crate rand;
+extern crate serde;
+extern crate serde_json;
 extern crate shader_version;
 
 mod common;
+pub mod field;
+pub mod geometry;
+pub mod phase;
 pub mod sound_source;
 pub mod view;
 
@@ -0,0 +1,34 @@
+/*
+ * File: error.rs
+ * Project: phase
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhaseError {
+    LengthMismatch { foci: usize, amps: usize },
+}
+
+impl fmt::Display for PhaseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PhaseError::LengthMismatch { foci, amps } => write!(
+                f,
+                "{} foci were given with {} amplitudes, expected one amplitude per focus",
+                foci, amps
+            ),
+        }
+    }
+}
+
+impl Error for PhaseError {}
@@ -0,0 +1,504 @@
+/*
+ * File: holo.rs
+ * Project: phase
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+//! Multi-focus holography.
+//!
+//! Every solver takes focal points `foci` with the desired pressure amplitudes `amps`,
+//! one per focus, and writes the resulting drive into the sources. Call `UpdateHandler::update_phase`
+//! afterwards to show the new pattern.
+
+use std::cmp::Ordering;
+
+use crate::field::{transfer, Complex};
+use crate::phase::PhaseError;
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+use crate::view::ViewerSettings;
+
+const LM_EPS_1: f64 = 1e-8;
+const LM_EPS_2: f64 = 1e-8;
+const LM_TAU: f64 = 1e-3;
+
+/// How solvers write `SoundSource::amp`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AmplitudeConstraint {
+    /// Only phases are written.
+    Keep,
+    /// Every source is driven at the given amplitude.
+    Uniform(f32),
+    /// Amplitudes found by the solver, scaled so that the largest one equals the given value.
+    Normalize(f32),
+}
+
+/// Superposition of single-focus patterns weighted by the target amplitudes.
+pub fn naive(
+    sources: &mut [SoundSource],
+    foci: &[Vector3],
+    amps: &[f32],
+    constraint: AmplitudeConstraint,
+    settings: &ViewerSettings,
+) -> Result<(), PhaseError> {
+    check_len(foci, amps)?;
+    let g = transfer_matrix(sources, foci, settings);
+    let p: Vec<Complex> = amps.iter().map(|&a| Complex::new(a, 0.)).collect();
+    let q = back_propagate(&g, &p, sources.len());
+    apply(sources, &q, constraint);
+    Ok(())
+}
+
+/// Gerchberg-Saxton: alternates between the target amplitudes at the foci
+/// and uniform amplitudes at the sources.
+pub fn gs(
+    sources: &mut [SoundSource],
+    foci: &[Vector3],
+    amps: &[f32],
+    repeat: usize,
+    constraint: AmplitudeConstraint,
+    settings: &ViewerSettings,
+) -> Result<(), PhaseError> {
+    check_len(foci, amps)?;
+    let g = transfer_matrix(sources, foci, settings);
+    let mut q = vec![Complex::new(1., 0.); sources.len()];
+    for _ in 0..repeat {
+        let gamma = propagate(&g, &q);
+        let p: Vec<Complex> = gamma
+            .iter()
+            .zip(amps.iter())
+            .map(|(g, &a)| unit(*g) * a)
+            .collect();
+        q = back_propagate(&g, &p, sources.len())
+            .into_iter()
+            .map(unit)
+            .collect();
+    }
+    apply(sources, &q, constraint);
+    Ok(())
+}
+
+/// GS-PAT (Plasencia et al., 2020): Gerchberg-Saxton iterated on the small
+/// `foci x foci` matrix `G B`, where `B` is the normalized back-propagation of `G`.
+pub fn gspat(
+    sources: &mut [SoundSource],
+    foci: &[Vector3],
+    amps: &[f32],
+    repeat: usize,
+    constraint: AmplitudeConstraint,
+    settings: &ViewerSettings,
+) -> Result<(), PhaseError> {
+    check_len(foci, amps)?;
+    let g = transfer_matrix(sources, foci, settings);
+    let b: Vec<Vec<Complex>> = g
+        .iter()
+        .map(|row| {
+            let norm: f32 = row.iter().map(|c| c.norm_sqr()).sum();
+            row.iter().map(|c| c.conj() / norm).collect()
+        })
+        .collect();
+    let m = foci.len();
+    let r: Vec<Vec<Complex>> = (0..m)
+        .map(|i| (0..m).map(|j| dot(&g[i], &b[j])).collect())
+        .collect();
+
+    let mut p: Vec<Complex> = amps.iter().map(|&a| Complex::new(a, 0.)).collect();
+    let mut gamma = propagate(&r, &p);
+    for _ in 0..repeat {
+        p = gamma
+            .iter()
+            .zip(amps.iter())
+            .map(|(g, &a)| unit(*g) * a)
+            .collect();
+        gamma = propagate(&r, &p);
+    }
+    // Compensate for the amplitude each focus actually receives.
+    let p: Vec<Complex> = gamma
+        .iter()
+        .zip(amps.iter())
+        .map(|(g, &a)| unit(*g) * (a * a / g.norm().max(f32::EPSILON)))
+        .collect();
+    let q = (0..sources.len())
+        .map(|n| b.iter().zip(p.iter()).map(|(row, p)| row[n] * p).sum())
+        .collect::<Vec<Complex>>();
+    apply(sources, &q, constraint);
+    Ok(())
+}
+
+/// Levenberg-Marquardt optimization of the source phases, following Levenberg-Marquardt
+/// holography in Long et al., "Rendering volumetric haptic shapes in mid-air using ultrasound" (2014).
+/// Runs at most `k_max` iterations.
+pub fn lm(
+    sources: &mut [SoundSource],
+    foci: &[Vector3],
+    amps: &[f32],
+    k_max: usize,
+    constraint: AmplitudeConstraint,
+    settings: &ViewerSettings,
+) -> Result<(), PhaseError> {
+    check_len(foci, amps)?;
+    let n = sources.len();
+    let m = foci.len();
+    let g = transfer_matrix(sources, foci, settings);
+
+    // B = [G, -diag(amps)], so that B exp(ix) vanishes when every focus has its target amplitude
+    // (the last m variables are the free phases at the foci).
+    let size = n + m;
+    let mut b = vec![vec![Complex::new(0., 0.); size]; m];
+    for i in 0..m {
+        for j in 0..n {
+            b[i][j] = g[i][j];
+        }
+        b[i][n + i] = Complex::new(-amps[i], 0.);
+    }
+    let bhb: Vec<Vec<Complex>> = (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| (0..m).map(|k| b[k][i].conj() * b[k][j]).sum())
+                .collect()
+        })
+        .collect();
+
+    let mut x = vec![0f64; size];
+    let (mut a, mut grad, mut fx) = lm_system(&bhb, &x);
+    let mut mu = LM_TAU * (0..size).map(|i| a[i][i]).fold(f64::MIN, f64::max);
+    let mut nu = 2.0;
+    for _ in 0..k_max {
+        if grad.iter().fold(0f64, |acc, g| acc.max(g.abs())) <= LM_EPS_1 {
+            break;
+        }
+
+        let mut damped = a.clone();
+        for (i, row) in damped.iter_mut().enumerate() {
+            row[i] += mu;
+        }
+        let h = match solve(damped, grad.iter().map(|g| -g).collect()) {
+            Some(h) => h,
+            None => break,
+        };
+        if norm(&h) <= LM_EPS_2 * (norm(&x) + LM_EPS_2) {
+            break;
+        }
+
+        let x_new: Vec<f64> = x.iter().zip(h.iter()).map(|(x, h)| x + h).collect();
+        let (a_new, grad_new, fx_new) = lm_system(&bhb, &x_new);
+        let gain: f64 = h
+            .iter()
+            .zip(grad.iter())
+            .map(|(h, g)| 0.5 * h * (mu * h - g))
+            .sum();
+        let rho = (fx - fx_new) / gain;
+        if rho > 0. {
+            x = x_new;
+            a = a_new;
+            grad = grad_new;
+            fx = fx_new;
+            mu *= (1. / 3f64).max(1. - (2. * rho - 1.).powi(3));
+            nu = 2.0;
+        } else {
+            mu *= nu;
+            nu *= 2.0;
+        }
+    }
+
+    let q: Vec<Complex> = x[..n]
+        .iter()
+        .map(|&phase| Complex::from_polar(&1.0, &(phase as f32)))
+        .collect();
+    apply(sources, &q, constraint);
+    Ok(())
+}
+
+fn check_len(foci: &[Vector3], amps: &[f32]) -> Result<(), PhaseError> {
+    if foci.len() == amps.len() {
+        Ok(())
+    } else {
+        Err(PhaseError::LengthMismatch {
+            foci: foci.len(),
+            amps: amps.len(),
+        })
+    }
+}
+
+/// `G[m][n]`: pressure at focus `m` from source `n` driven with unit amplitude and zero phase.
+fn transfer_matrix(
+    sources: &[SoundSource],
+    foci: &[Vector3],
+    settings: &ViewerSettings,
+) -> Vec<Vec<Complex>> {
+    foci.iter()
+        .map(|&focus| {
+            sources
+                .iter()
+                .map(|source| transfer(focus, source, settings))
+                .collect()
+        })
+        .collect()
+}
+
+/// `G q`
+fn propagate(g: &[Vec<Complex>], q: &[Complex]) -> Vec<Complex> {
+    g.iter().map(|row| dot(row, q)).collect()
+}
+
+/// `G^H p`
+fn back_propagate(g: &[Vec<Complex>], p: &[Complex], n: usize) -> Vec<Complex> {
+    (0..n)
+        .map(|j| {
+            g.iter()
+                .zip(p.iter())
+                .map(|(row, p)| row[j].conj() * p)
+                .sum()
+        })
+        .collect()
+}
+
+fn dot(l: &[Complex], r: &[Complex]) -> Complex {
+    l.iter().zip(r.iter()).map(|(l, r)| l * r).sum()
+}
+
+fn unit(c: Complex) -> Complex {
+    let norm = c.norm();
+    if norm > 0. {
+        c / norm
+    } else {
+        Complex::new(1., 0.)
+    }
+}
+
+fn apply(sources: &mut [SoundSource], q: &[Complex], constraint: AmplitudeConstraint) {
+    let max = q.iter().fold(0f32, |acc, c| acc.max(c.norm()));
+    for (source, c) in sources.iter_mut().zip(q.iter()) {
+        source.phase = c.arg().rem_euclid(2.0 * std::f32::consts::PI);
+        match constraint {
+            AmplitudeConstraint::Keep => (),
+            AmplitudeConstraint::Uniform(amp) => source.amp = amp,
+            AmplitudeConstraint::Normalize(amp) => {
+                source.amp = if max > 0. { amp * c.norm() / max } else { 0. }
+            }
+        }
+    }
+}
+
+/// Approximated Hessian `Re(BhB o T T^H)`, gradient `Im(sum_j (BhB o T T^H)_ij)`
+/// and cost `T^H BhB T` at `x`, where `T = exp(ix)`.
+fn lm_system(bhb: &[Vec<Complex>], x: &[f64]) -> (Vec<Vec<f64>>, Vec<f64>, f64) {
+    let t: Vec<num_complex::Complex<f64>> = x
+        .iter()
+        .map(|&x| num_complex::Complex::from_polar(&1.0, &x))
+        .collect();
+    let size = x.len();
+    let mut a = vec![vec![0f64; size]; size];
+    let mut grad = vec![0f64; size];
+    let mut fx = 0f64;
+    for i in 0..size {
+        for j in 0..size {
+            let c = bhb[i][j];
+            let v =
+                num_complex::Complex::new(f64::from(c.re), f64::from(c.im)) * t[i].conj() * t[j];
+            a[i][j] = v.re;
+            grad[i] += v.im;
+            fx += v.re;
+        }
+    }
+    (a, grad, fx)
+}
+
+/// Solves `a x = b` by Gaussian elimination with partial pivoting.
+/// Returns `None` if `a` is singular or not finite, e.g. when a focus lies on a source.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| {
+            a[i][col]
+                .abs()
+                .partial_cmp(&a[j][col].abs())
+                .unwrap_or(Ordering::Equal)
+        })?;
+        if !a[pivot][col].is_finite() || a[pivot][col].abs() < f64::EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (i, row) in lower.iter_mut().enumerate() {
+            let f = row[col] / pivot_row[col];
+            for (v, p) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+                *v -= f * p;
+            }
+            b[col + 1 + i] -= f * b[col];
+        }
+    }
+    let mut x = vec![0f64; n];
+    for row in (0..n).rev() {
+        let s: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - s) / a[row][row];
+    }
+    Some(x)
+}
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::field::calc_pressure;
+
+    const FOCI: [Vector3; 2] = [[25., 45., 150.], [75., 45., 150.]];
+    const AMPS: [f32; 2] = [0.3, 0.15];
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    fn grid() -> Vec<SoundSource> {
+        (0..100)
+            .map(|i| {
+                let pos = [10.18 * (i % 10) as f32, 10.18 * (i / 10) as f32, 0.];
+                SoundSource::new(pos, [0., 0., 1.], 0.)
+            })
+            .collect()
+    }
+
+    fn focal_amps(sources: &[SoundSource], settings: &ViewerSettings) -> Vec<f32> {
+        FOCI.iter()
+            .map(|&f| calc_pressure(f, sources, settings).norm())
+            .collect()
+    }
+
+    // The overall scale depends on the amplitude constraint, so it is checked for `lm` alone.
+    fn assert_ratio(p: &[f32], amps: &[f32]) {
+        let ratio = p[1] / p[0];
+        let expected = amps[1] / amps[0];
+        assert!(
+            (ratio - expected).abs() < 0.1 * expected,
+            "{:?} does not match {:?}",
+            p,
+            amps
+        );
+    }
+
+    #[test]
+    fn naive_reaches_targets() {
+        let settings = settings();
+        let mut sources = grid();
+        naive(
+            &mut sources,
+            &FOCI,
+            &AMPS,
+            AmplitudeConstraint::Normalize(1.),
+            &settings,
+        )
+        .unwrap();
+        assert_ratio(&focal_amps(&sources, &settings), &AMPS);
+    }
+
+    #[test]
+    fn gs_reaches_targets() {
+        // Plain GS does not keep unequal ratios between foci, which is what GS-PAT compensates for.
+        let amps = [0.3, 0.3];
+        let settings = settings();
+        let mut sources = grid();
+        gs(
+            &mut sources,
+            &FOCI,
+            &amps,
+            100,
+            AmplitudeConstraint::Uniform(1.),
+            &settings,
+        )
+        .unwrap();
+        assert_ratio(&focal_amps(&sources, &settings), &amps);
+    }
+
+    #[test]
+    fn gspat_reaches_targets() {
+        let settings = settings();
+        let mut sources = grid();
+        gspat(
+            &mut sources,
+            &FOCI,
+            &AMPS,
+            100,
+            AmplitudeConstraint::Normalize(1.),
+            &settings,
+        )
+        .unwrap();
+        assert_ratio(&focal_amps(&sources, &settings), &AMPS);
+    }
+
+    #[test]
+    fn lm_reaches_targets() {
+        let settings = settings();
+        let mut sources = grid();
+        lm(
+            &mut sources,
+            &FOCI,
+            &AMPS,
+            20,
+            AmplitudeConstraint::Uniform(1.),
+            &settings,
+        )
+        .unwrap();
+        let p = focal_amps(&sources, &settings);
+        for (p, a) in p.iter().zip(AMPS.iter()) {
+            assert!((p - a).abs() < 0.1 * a, "{:?} does not match {:?}", p, AMPS);
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_amplitudes() {
+        let settings = settings();
+        let mut sources = grid();
+        let err = Err(PhaseError::LengthMismatch { foci: 2, amps: 1 });
+        let constraint = AmplitudeConstraint::Keep;
+        assert_eq!(
+            naive(&mut sources, &FOCI, &[1.], constraint, &settings),
+            err
+        );
+        assert_eq!(
+            gs(&mut sources, &FOCI, &[1.], 10, constraint, &settings),
+            err
+        );
+        assert_eq!(
+            gspat(&mut sources, &FOCI, &[1.], 10, constraint, &settings),
+            err
+        );
+        assert_eq!(
+            lm(&mut sources, &FOCI, &[1.], 10, constraint, &settings),
+            err
+        );
+    }
+
+    #[test]
+    fn lm_survives_focus_on_source() {
+        let settings = settings();
+        let mut sources = grid();
+        let foci = [sources[0].pos, FOCI[1]];
+        lm(
+            &mut sources,
+            &foci,
+            &AMPS,
+            10,
+            AmplitudeConstraint::Keep,
+            &settings,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * File: mod.rs
+ * Project: phase
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+//! Phase patterns written into `SoundSource::phase`.
+//!
+//! A source contributes `amp * exp(i(phase - k * d)) / d` at distance `d`,
+//! the same model as `field::calc_pressure` and the slice shader.
+
+mod error;
+mod holo;
+mod primitive;
+mod trap;
+
+pub use error::PhaseError;
+pub use holo::{gs, gspat, lm, naive, AmplitudeConstraint};
+pub use primitive::{bessel_beam, focus, plane_wave, vortex};
+pub use trap::{bottle_beam, twin_trap, vortex_trap};
@@ -0,0 +1,154 @@
+/*
+ * File: primitive.rs
+ * Project: phase
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::f32::consts::PI;
+
+use crate::sound_source::SoundSource;
+use crate::vec_utils;
+use crate::vec_utils::Vector3;
+use crate::view::ViewerSettings;
+
+/// All waves arrive at `point` in phase.
+pub fn focus(sources: &mut [SoundSource], point: Vector3, settings: &ViewerSettings) {
+    let k = settings.wave_num();
+    for source in sources.iter_mut() {
+        source.phase = wrap(k * vec_utils::dist(source.pos, point));
+    }
+}
+
+/// Plane wave travelling along `dir`.
+pub fn plane_wave(sources: &mut [SoundSource], dir: Vector3, settings: &ViewerSettings) {
+    let k = settings.wave_num();
+    let n = vecmath::vec3_normalized(dir);
+    for source in sources.iter_mut() {
+        source.phase = wrap(-k * vecmath::vec3_dot(source.pos, n));
+    }
+}
+
+/// Bessel beam along `dir` from `apex`, made of waves converging on the axis at `theta` radians.
+pub fn bessel_beam(
+    sources: &mut [SoundSource],
+    apex: Vector3,
+    dir: Vector3,
+    theta: f32,
+    settings: &ViewerSettings,
+) {
+    let k = settings.wave_num();
+    let n = vecmath::vec3_normalized(dir);
+    for source in sources.iter_mut() {
+        let r = vecmath::vec3_sub(source.pos, apex);
+        let z = vecmath::vec3_dot(r, n);
+        let rho = vecmath::vec3_len(vecmath::vec3_sub(r, vecmath::vec3_scale(n, z)));
+        source.phase = wrap(k * (theta.sin() * rho - theta.cos() * z));
+    }
+}
+
+/// Focus at `point` carrying orbital angular momentum of topological `charge` around the axis `dir`.
+pub fn vortex(
+    sources: &mut [SoundSource],
+    point: Vector3,
+    dir: Vector3,
+    charge: i32,
+    settings: &ViewerSettings,
+) {
+    let k = settings.wave_num();
+    let (u, v) = vec_utils::orthonormal_basis(dir);
+    for source in sources.iter_mut() {
+        let r = vecmath::vec3_sub(source.pos, point);
+        let azimuth = vecmath::vec3_dot(r, v).atan2(vecmath::vec3_dot(r, u));
+        source.phase = wrap(k * vecmath::vec3_len(r) + charge as f32 * azimuth);
+    }
+}
+
+fn wrap(phase: f32) -> f32 {
+    phase.rem_euclid(2.0 * PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::field::{calc_pressure, Complex};
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    // 16 sources on the x-axis, half a wavelength apart and centered on the origin.
+    fn line(settings: &ViewerSettings) -> Vec<SoundSource> {
+        let pitch = settings.wave_length() / 2.;
+        (0..16)
+            .map(|i| SoundSource::new([pitch * (i as f32 - 7.5), 0., 0.], [0., 0., 1.], 0.))
+            .collect()
+    }
+
+    fn unit(phase: f32) -> Complex {
+        Complex::from_polar(&1., &phase)
+    }
+
+    #[test]
+    fn focus_aligns_contributions_at_point() {
+        let settings = settings();
+        let mut sources = line(&settings);
+        let point = [20., 10., 80.];
+        focus(&mut sources, point, &settings);
+        for source in &sources {
+            let p = calc_pressure(point, &[*source], &settings);
+            assert!((p / p.norm() - 1.).norm() < 1e-3, "arg = {}", p.arg());
+        }
+
+        let peak = calc_pressure(point, &sources, &settings).norm();
+        // Along the axis the 1/d decay shifts the maximum towards the array, so only look sideways.
+        let quarter = settings.wave_length() / 4.;
+        for &offset in &[[quarter, 0., 0.], [-quarter, 0., 0.], [0., quarter, 0.]] {
+            let p = calc_pressure(vecmath::vec3_add(point, offset), &sources, &settings);
+            assert!(p.norm() < peak);
+        }
+    }
+
+    #[test]
+    fn plane_wave_steers_along_dir() {
+        let settings = settings();
+        let mut sources = line(&settings);
+        let angle = PI / 6.;
+        plane_wave(&mut sources, [angle.sin(), 0., angle.cos()], &settings);
+
+        let distance = 1e4;
+        let far = |angle: f32| [distance * angle.sin(), 0., distance * angle.cos()];
+        let steered = calc_pressure(far(angle), &sources, &settings).norm() * distance;
+        let mirrored = calc_pressure(far(-angle), &sources, &settings).norm() * distance;
+        assert!((steered - 16.).abs() < 0.2, "{}", steered);
+        assert!(mirrored < 0.3 * 16., "{}", mirrored);
+    }
+
+    #[test]
+    fn far_focus_matches_plane_wave() {
+        let settings = settings();
+        let dir = vecmath::vec3_normalized([0.3, 0., 1.]);
+        let mut plane = line(&settings);
+        plane_wave(&mut plane, dir, &settings);
+        let mut focused = line(&settings);
+        focus(&mut focused, vecmath::vec3_scale(dir, 1e5), &settings);
+
+        // Equal up to a common offset.
+        let offset = unit(focused[0].phase - plane[0].phase);
+        for (f, p) in focused.iter().zip(plane.iter()) {
+            assert!((unit(f.phase - p.phase) - offset).norm() < 2e-2);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+/*
+ * File: trap.rs
+ * Project: phase
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+//! Levitation traps after Marzo et al., "Holographic acoustic elements for
+//! manipulation of levitated objects" (2015). Each one is a focus at `point`
+//! with a phase signature added on top.
+
+use std::f32::consts::PI;
+
+use crate::field::transfer;
+use crate::phase::{focus, vortex};
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Vector3;
+use crate::view::ViewerSettings;
+
+/// Twin trap: sources on the side of the plane through `point` that `normal` points to
+/// are delayed by half a period, leaving a pressure null on that plane.
+pub fn twin_trap(
+    sources: &mut [SoundSource],
+    point: Vector3,
+    normal: Vector3,
+    settings: &ViewerSettings,
+) {
+    focus(sources, point, settings);
+    for source in sources.iter_mut() {
+        if vecmath::vec3_dot(vecmath::vec3_sub(source.pos, point), normal) > 0. {
+            source.phase = (source.phase + PI) % (2.0 * PI);
+        }
+    }
+}
+
+/// Vortex trap: a focus with a helical phase of charge 1 around the axis `dir`.
+pub fn vortex_trap(
+    sources: &mut [SoundSource],
+    point: Vector3,
+    dir: Vector3,
+    settings: &ViewerSettings,
+) {
+    vortex(sources, point, dir, 1, settings);
+}
+
+/// Bottle beam: the sources closest to the axis through `point` along `dir` are delayed by
+/// half a period. They are chosen so that they supply half of the pressure at the focus,
+/// which leaves a low-pressure region enclosed around `point`.
+pub fn bottle_beam(
+    sources: &mut [SoundSource],
+    point: Vector3,
+    dir: Vector3,
+    settings: &ViewerSettings,
+) {
+    focus(sources, point, settings);
+    let n = vecmath::vec3_normalized(dir);
+    let mut order: Vec<(usize, f32, f32)> = sources
+        .iter()
+        .enumerate()
+        .map(|(i, source)| {
+            let r = vecmath::vec3_sub(source.pos, point);
+            let axial = vecmath::vec3_scale(n, vecmath::vec3_dot(r, n));
+            let rho = vecmath::vec3_len(vecmath::vec3_sub(r, axial));
+            let contribution = source.amp * transfer(point, source, settings).norm();
+            (i, rho, contribution)
+        })
+        .collect();
+    order.sort_by(|l, r| l.1.total_cmp(&r.1));
+
+    let half = order.iter().map(|o| o.2).sum::<f32>() / 2.;
+    let mut acc = 0.;
+    for (i, _, contribution) in order {
+        if acc >= half {
+            break;
+        }
+        acc += contribution;
+        sources[i].phase = (sources[i].phase + PI) % (2.0 * PI);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coloring_method::coloring_hsv;
+    use crate::field::calc_pressure;
+
+    fn settings() -> ViewerSettings {
+        ViewerSettings::new(
+            40e3,
+            10.18,
+            coloring_hsv,
+            scarlet::colormap::ListedColorMap::inferno(),
+        )
+    }
+
+    // 6 x 6 grid centered on the z-axis.
+    fn grid() -> Vec<SoundSource> {
+        (0..36)
+            .map(|i| {
+                let pos = [
+                    10.18 * ((i % 6) as f32 - 2.5),
+                    10.18 * ((i / 6) as f32 - 2.5),
+                    0.,
+                ];
+                SoundSource::new(pos, [0., 0., 1.], 0.)
+            })
+            .collect()
+    }
+
+    // Phase difference wrapped to [0, 2 PI).
+    fn phase_shift(trap: &SoundSource, focus: &SoundSource) -> f32 {
+        (trap.phase - focus.phase).rem_euclid(2. * PI)
+    }
+
+    fn is_flipped(trap: &SoundSource, focus: &SoundSource) -> bool {
+        let shift = phase_shift(trap, focus);
+        assert!(
+            shift.min(2. * PI - shift) < 1e-4 || (shift - PI).abs() < 1e-4,
+            "unexpected phase shift {}",
+            shift
+        );
+        (shift - PI).abs() < 1e-4
+    }
+
+    #[test]
+    fn twin_trap_flips_one_side() {
+        let settings = settings();
+        let point = [0., 0., 100.];
+        let mut expected = grid();
+        focus(&mut expected, point, &settings);
+        let mut sources = grid();
+        twin_trap(&mut sources, point, [1., 0., 0.], &settings);
+
+        for (trap, focus) in sources.iter().zip(expected.iter()) {
+            assert_eq!(is_flipped(trap, focus), trap.pos[0] > 0.);
+        }
+        let null = calc_pressure(point, &sources, &settings).norm();
+        let peak = calc_pressure(point, &expected, &settings).norm();
+        assert!(null < 1e-3 * peak);
+    }
+
+    #[test]
+    fn bottle_beam_flips_center_sources() {
+        let settings = settings();
+        let point = [0., 0., 100.];
+        let mut expected = grid();
+        focus(&mut expected, point, &settings);
+        let mut sources = grid();
+        bottle_beam(&mut sources, point, [0., 0., 1.], &settings);
+
+        let rho = |s: &SoundSource| s.pos[0].hypot(s.pos[1]);
+        let (flipped, kept): (Vec<_>, Vec<_>) = sources
+            .iter()
+            .zip(expected.iter())
+            .partition(|(trap, focus)| is_flipped(trap, focus));
+        assert!(!flipped.is_empty() && !kept.is_empty());
+        let max_flipped = flipped.iter().map(|(s, _)| rho(s)).fold(0f32, f32::max);
+        let min_kept = kept.iter().map(|(s, _)| rho(s)).fold(f32::MAX, f32::min);
+        assert!(max_flipped <= min_kept);
+
+        let center = calc_pressure(point, &sources, &settings).norm();
+        let peak = calc_pressure(point, &expected, &settings).norm();
+        assert!(center < 0.2 * peak);
+    }
+
+    #[test]
+    fn bottle_beam_accepts_nan_positions() {
+        let settings = settings();
+        let mut sources = grid();
+        sources[0].pos = [f32::NAN; 3];
+        bottle_beam(&mut sources, [0., 0., 100.], [0., 0., 1.], &settings);
+    }
+}
@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 28/04/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -18,10 +18,20 @@ pub struct SoundSource {
     pub pos: Vector3,
     pub dir: Vector3,
     pub phase: f32,
+    pub amp: f32,
 }
 
 impl SoundSource {
     pub fn new(pos: Vector3, dir: Vector3, phase: f32) -> SoundSource {
-        SoundSource { pos, dir, phase }
+        SoundSource::with_amp(pos, dir, phase, 1.0)
+    }
+
+    pub fn with_amp(pos: Vector3, dir: Vector3, phase: f32, amp: f32) -> SoundSource {
+        SoundSource {
+            pos,
+            dir,
+            phase,
+            amp,
+        }
     }
 }
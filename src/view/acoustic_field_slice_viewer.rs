@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 29/04/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -18,8 +18,8 @@ use gfx::format;
 use gfx::handle::{Buffer, DepthStencilView, RenderTargetView, ShaderResourceView};
 use gfx::preset::depth;
 use gfx::state::{Blend, ColorMask};
+use gfx::texture::{AaMode, Kind, Mipmap};
 use gfx::texture::{FilterMethod, SamplerInfo, WrapMode};
-use gfx::texture::{Kind, Mipmap};
 use gfx::traits::*;
 use gfx::{BlendTarget, DepthTarget, Global, PipelineState, Slice, TextureSampler, VertexBuffer};
 use gfx_device_gl::Resources;
@@ -32,10 +32,12 @@ use shader_version::Shaders;
 use std::cell::RefCell;
 use std::rc::Weak;
 
+use crate::field::{image_sources, FieldQuantity, SliceField};
 use crate::sound_source::SoundSource;
 use crate::vec_utils;
 use crate::vec_utils::{Matrix4, Vector3};
-use crate::view::ViewerSettings;
+use crate::view::force_arrows::ForceArrows;
+use crate::view::{ViewError, ViewerSettings};
 
 gfx_vertex_struct!(Vertex {
     a_pos: [i8; 4] = "a_pos",
@@ -58,18 +60,27 @@ fn alpha_blender() -> Blend {
     )
 }
 
+const DIRECTIVITY_TABLE_SIZE: usize = 181;
+
 gfx_pipeline!( pipe {
     vertex_buffer: VertexBuffer<Vertex> = (),
     u_model_view_proj: Global<[[f32; 4]; 4]> = "u_model_view_proj",
     u_model: Global<[[f32; 4]; 4]> = "u_model",
-    u_trans_size : Global<f32> = "u_trans_size",
+    u_wave_num : Global<f32> = "u_wave_num",
+    u_attenuation : Global<f32> = "u_attenuation",
+    u_quantity : Global<i32> = "u_quantity",
+    u_spl_reference : Global<f32> = "u_spl_reference",
+    u_gorkov_k1 : Global<f32> = "u_gorkov_k1",
+    u_gorkov_k2 : Global<f32> = "u_gorkov_k2",
+    u_time_phase : Global<f32> = "u_time_phase",
     u_color_scale : Global<f32> = "u_color_scale",
     u_color_map: TextureSampler<[f32; 4]> = "u_color_map",
     u_trans_num : Global<f32> = "u_trans_num",
     u_trans_pos: TextureSampler<[f32; 4]> = "u_trans_pos",
-    u_trans_pos_256: TextureSampler<[f32; 4]> = "u_trans_pos_256",
-    u_trans_pos_sub: TextureSampler<[f32; 4]> = "u_trans_pos_sub",
     u_trans_phase: TextureSampler<[f32; 4]> = "u_trans_phase",
+    u_trans_amp: TextureSampler<[f32; 4]> = "u_trans_amp",
+    u_trans_dir: TextureSampler<[f32; 4]> = "u_trans_dir",
+    u_directivity: TextureSampler<[f32; 4]> = "u_directivity",
     out_color: BlendTarget<format::Srgba8> = ("o_Color", ColorMask::all(), alpha_blender()),
     out_depth: DepthTarget<format::DepthStencil> = depth::LESS_EQUAL_WRITE,
 });
@@ -78,11 +89,27 @@ pub struct AcousticFiledSliceViewer {
     pub(crate) settings: Weak<RefCell<ViewerSettings>>,
     pub(crate) sources: Weak<RefCell<Vec<SoundSource>>>,
     pipe_data: Option<pipe::Data<Resources>>,
-    model: Matrix4,
+    pose: Matrix4,
+    size: [f32; 2],
     pso_slice: Option<(PipelineState<Resources, pipe::Meta>, Slice<Resources>)>,
     position_updated: bool,
     phase_updated: bool,
     colomap_updated: bool,
+    settings_updated: bool,
+    model_updated: bool,
+    source_texture_width: usize,
+    time_phase: f32,
+    force_arrows: Option<ForceArrows>,
+    field_quantity: Option<FieldQuantity>,
+    alpha: Option<f32>,
+    // Number of emitters last reported as too many, so the error is returned once.
+    reported_overflow: Option<usize>,
+}
+
+impl Default for AcousticFiledSliceViewer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl AcousticFiledSliceViewer {
@@ -91,16 +118,52 @@ impl AcousticFiledSliceViewer {
             settings: Weak::new(),
             pipe_data: None,
             sources: Weak::new(),
-            model: vec_utils::mat4_scale(150.),
+            pose: vecmath::mat4_id(),
+            size: [300., 300.],
             pso_slice: None,
             position_updated: false,
             phase_updated: false,
             colomap_updated: false,
+            settings_updated: false,
+            model_updated: false,
+            source_texture_width: 1,
+            time_phase: 0.,
+            force_arrows: None,
+            field_quantity: None,
+            alpha: None,
+            reported_overflow: None,
         }
     }
 
-    pub fn render_setting(&mut self, window: &PistonWindow, opengl: OpenGL) {
-        let factory = &mut window.factory.clone();
+    pub fn render_setting(
+        &mut self,
+        window: &PistonWindow,
+        opengl: OpenGL,
+    ) -> Result<(), ViewError> {
+        self.render_setting_with_target(
+            &mut window.factory.clone(),
+            opengl,
+            window.output_color.clone(),
+            window.output_stencil.clone(),
+        )
+    }
+
+    pub(crate) fn render_setting_with_target(
+        &mut self,
+        factory: &mut gfx_device_gl::Factory,
+        opengl: OpenGL,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) -> Result<(), ViewError> {
+        let len = self.sources.upgrade().unwrap().borrow().len();
+        self.source_texture_width = factory
+            .get_capabilities()
+            .max_texture_size
+            .min(u16::MAX as usize);
+        let max = self.source_texture_width * self.source_texture_width;
+        if len > max {
+            return Err(ViewError::TooManySources { num: len, max });
+        }
 
         let vertex_data = vec![
             Vertex::new([-1, 0, -1]),
@@ -114,21 +177,20 @@ impl AcousticFiledSliceViewer {
 
         let glsl = opengl.to_glsl();
         self.initialize_shader(factory, glsl, slice);
-
-        let len = self.sources.upgrade().unwrap().borrow().len();
-        let phase_view = AcousticFiledSliceViewer::generate_empty_view(factory, len);
-
-        self.initialize_pipe_data(
+        self.force_arrows = Some(ForceArrows::new(
             factory,
-            vertex_buffer,
-            phase_view,
-            window.output_color.clone(),
-            window.output_stencil.clone(),
-        );
+            glsl,
+            out_color.clone(),
+            out_depth.clone(),
+        ));
+
+        self.initialize_pipe_data(factory, vertex_buffer, out_color, out_depth);
 
         self.update_source_pos();
         self.update_source_phase();
         self.update_color_map();
+        self.update_settings();
+        Ok(())
     }
 
     pub fn update_source_pos(&mut self) {
@@ -143,16 +205,99 @@ impl AcousticFiledSliceViewer {
         self.colomap_updated = true;
     }
 
+    pub fn update_settings(&mut self) {
+        self.settings_updated = true;
+    }
+
+    /// Shows `quantity` on this slice instead of `ViewerSettings::field_quantity`,
+    /// or follows the settings again with `None`.
+    pub fn set_field_quantity(&mut self, quantity: Option<FieldQuantity>) {
+        self.field_quantity = quantity;
+        self.settings_updated = true;
+    }
+
+    /// Same as `set_field_quantity` for `ViewerSettings::slice_alpha`.
+    pub fn set_alpha(&mut self, alpha: Option<f32>) {
+        self.alpha = alpha;
+        self.colomap_updated = true;
+    }
+
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.pipe_data.is_some()
+    }
+
     pub fn translate(&mut self, travel: Vector3) {
-        self.model[3][0] += travel[0];
-        self.model[3][1] += travel[1];
-        self.model[3][2] += travel[2];
+        self.pose[3][0] += travel[0];
+        self.pose[3][1] += travel[1];
+        self.pose[3][2] += travel[2];
+        self.model_updated = true;
     }
 
     pub fn rotate(&mut self, axis: Vector3, rot: f32) {
         let rot = quaternion::axis_angle(axis, rot);
         let rotm = vec_utils::mat4_rot(rot);
-        self.model = vecmath::col_mat4_mul(self.model, rotm);
+        self.pose = vecmath::col_mat4_mul(self.pose, rotm);
+        self.model_updated = true;
+    }
+
+    /// Sets the extent in mm along the local x axis (`width`) and the local z axis (`height`),
+    /// keeping the center and orientation.
+    pub fn set_size(&mut self, width: f32, height: f32) {
+        self.size = [width, height];
+        self.model_updated = true;
+    }
+
+    pub fn size(&self) -> [f32; 2] {
+        self.size
+    }
+
+    /// Places the slice on the rectangle spanned by `u` and `v` from the corner `origin`.
+    /// The slice gets the width `|u|` along `u` and the height along `v`;
+    /// `v` is made perpendicular to `u` first.
+    pub fn set_plane(&mut self, origin: Vector3, u: Vector3, v: Vector3) {
+        let x = vecmath::vec3_normalized(u);
+        let v = vecmath::vec3_sub(v, vecmath::vec3_scale(x, vecmath::vec3_dot(v, x)));
+        let z = vecmath::vec3_normalized(v);
+        let y = vecmath::vec3_cross(z, x);
+        let center = vecmath::vec3_add(origin, vecmath::vec3_scale(vecmath::vec3_add(u, v), 0.5));
+        self.pose = [
+            vec_utils::to_vec4(x),
+            vec_utils::to_vec4(y),
+            vec_utils::to_vec4(z),
+            vec_utils::to_point4(center),
+        ];
+        self.set_size(vecmath::vec3_len(u), vecmath::vec3_len(v));
+    }
+
+    /// Transform from the unit quad `[-1, 1] x {0} x [-1, 1]` to the slice in world coordinates.
+    pub fn model(&self) -> Matrix4 {
+        vecmath::col_mat4_mul(
+            self.pose,
+            vec_utils::mat4_scale3([self.size[0] / 2., 1., self.size[1] / 2.]),
+        )
+    }
+
+    /// Samples the complex pressure on the slice currently shown on a `width` x `height` grid.
+    pub fn sample_field(&self, width: usize, height: usize) -> SliceField {
+        SliceField::sample(
+            self.model(),
+            width,
+            height,
+            &self.sources.upgrade().unwrap().borrow(),
+            &self.settings.upgrade().unwrap().borrow(),
+        )
+    }
+
+    /// Same as `sample_field` with samples about `pitch` mm apart, including the edges.
+    pub fn sample_field_with_pitch(&self, pitch: f32) -> SliceField {
+        let width = (self.size[0] / pitch).round() as usize + 1;
+        let height = (self.size[1] / pitch).round() as usize + 1;
+        self.sample_field(width, height)
+    }
+
+    /// `omega * t` shown when `ViewerSettings::animate` is on, shared by all slices in a view.
+    pub(crate) fn set_time_phase(&mut self, time_phase: f32) {
+        self.time_phase = time_phase;
     }
 
     pub fn renderer(
@@ -161,87 +306,212 @@ impl AcousticFiledSliceViewer {
         event: &Event,
         view: Matrix4,
         projection: Matrix4,
-    ) {
-        window.draw_3d(event, |window| {
-            if let Some(data) = &mut self.pipe_data {
-                if self.phase_updated {
-                    AcousticFiledSliceViewer::update_phase_texture(
-                        data,
-                        &mut window.factory,
-                        &self.sources.upgrade().unwrap().borrow(),
-                    );
-                    self.phase_updated = false;
-                }
+    ) -> Result<(), ViewError> {
+        let result = window
+            .draw_3d(event, |window| {
+                self.render(&mut window.encoder, &mut window.factory, view, projection)
+            })
+            .unwrap_or(Ok(()));
+        if event.resize_args().is_some() {
+            self.set_render_target(window.output_color.clone(), window.output_stencil.clone());
+        }
+        result
+    }
 
-                if self.position_updated {
-                    let source_size = self.settings.upgrade().unwrap().borrow().source_size;
-                    let source_num = self.sources.upgrade().unwrap().borrow().len();
-                    data.u_trans_num = source_num as f32;
-                    AcousticFiledSliceViewer::update_position_texture(
-                        data,
-                        &mut window.factory,
-                        &self.sources.upgrade().unwrap().borrow(),
-                        source_size,
-                    );
-                    self.position_updated = false;
-                }
+    pub(crate) fn render(
+        &mut self,
+        encoder: &mut GfxEncoder,
+        factory: &mut gfx_device_gl::Factory,
+        view: Matrix4,
+        projection: Matrix4,
+    ) -> Result<(), ViewError> {
+        if self.settings_updated {
+            // Reflectors change the image sources uploaded with the real ones.
+            self.phase_updated = true;
+            self.position_updated = true;
+        }
+        let field_changed = self.phase_updated
+            || self.position_updated
+            || self.settings_updated
+            || self.model_updated;
+        let model = self.model();
+        let emitters = if self.phase_updated || self.position_updated {
+            let settings = self.settings.upgrade().unwrap();
+            let settings = settings.borrow();
+            image_sources(
+                &self.sources.upgrade().unwrap().borrow(),
+                &settings.reflectors,
+                settings.reflection_order,
+            )
+        } else {
+            vec![]
+        };
+        // Image sources can outgrow the textures even if the real sources fit.
+        // The previous sources are kept on the slice in that case,
+        // and the error is returned only the first time for a given count.
+        let max = self.source_texture_width * self.source_texture_width;
+        let result = if emitters.len() > max {
+            self.phase_updated = false;
+            self.position_updated = false;
+            if self.reported_overflow == Some(emitters.len()) {
+                Ok(())
+            } else {
+                self.reported_overflow = Some(emitters.len());
+                Err(ViewError::TooManySources {
+                    num: emitters.len(),
+                    max,
+                })
+            }
+        } else {
+            if self.phase_updated || self.position_updated {
+                self.reported_overflow = None;
+            }
+            Ok(())
+        };
+        if let Some(data) = &mut self.pipe_data {
+            if self.phase_updated {
+                AcousticFiledSliceViewer::update_phase_texture(
+                    data,
+                    factory,
+                    &emitters,
+                    self.source_texture_width,
+                );
+                AcousticFiledSliceViewer::update_amp_texture(
+                    data,
+                    factory,
+                    &emitters,
+                    self.source_texture_width,
+                );
+                self.phase_updated = false;
+            }
 
-                if self.colomap_updated {
-                    let iter = (0..100).map(|x| x as f64 / 100.0);
-                    let colors = self
-                        .settings
-                        .upgrade()
-                        .unwrap()
-                        .borrow()
-                        .field_color_map
-                        .transform(iter);
-                    let alpha = self.settings.upgrade().unwrap().borrow().slice_alpha;
-                    AcousticFiledSliceViewer::update_color_map_texture(
-                        data,
-                        &mut window.factory,
-                        &colors,
-                        alpha,
-                    );
-                    data.u_color_scale = self.settings.upgrade().unwrap().borrow().color_scale;
-                    self.colomap_updated = false;
-                }
+            if self.position_updated {
+                data.u_trans_num = emitters.len() as f32;
+                AcousticFiledSliceViewer::update_position_texture(
+                    data,
+                    factory,
+                    &emitters,
+                    self.source_texture_width,
+                );
+                AcousticFiledSliceViewer::update_direction_texture(
+                    data,
+                    factory,
+                    &emitters,
+                    self.source_texture_width,
+                );
+                self.position_updated = false;
+            }
 
-                data.u_model = self.model;
-                data.u_model_view_proj = model_view_projection(self.model, view, projection);
-                if let Some(pso_slice) = &self.pso_slice {
-                    window.encoder.draw(&pso_slice.1, &pso_slice.0, data);
-                }
+            if self.colomap_updated {
+                let iter = (0..100).map(|x| x as f64 / 100.0);
+                let colors = self
+                    .settings
+                    .upgrade()
+                    .unwrap()
+                    .borrow()
+                    .field_color_map
+                    .transform(iter);
+                let alpha = self
+                    .alpha
+                    .unwrap_or(self.settings.upgrade().unwrap().borrow().slice_alpha);
+                AcousticFiledSliceViewer::update_color_map_texture(data, factory, &colors, alpha);
+                data.u_color_scale = self.settings.upgrade().unwrap().borrow().color_scale;
+                self.colomap_updated = false;
+            }
+
+            if self.settings_updated {
+                let settings = self.settings.upgrade().unwrap();
+                let settings = settings.borrow();
+                data.u_wave_num = settings.wave_num();
+                data.u_attenuation =
+                    settings.medium.attenuation_coefficient(settings.freqency) * 1e-3;
+                let quantity = self.field_quantity.unwrap_or(settings.field_quantity);
+                data.u_quantity = quantity.shader_id();
+                data.u_spl_reference = quantity.spl_reference();
+                let (k1, k2) = settings.particle.gorkov_coefficients(&settings);
+                data.u_gorkov_k1 = k1;
+                data.u_gorkov_k2 = k2;
+                AcousticFiledSliceViewer::update_directivity_texture(
+                    data,
+                    factory,
+                    &settings
+                        .directivity
+                        .table(DIRECTIVITY_TABLE_SIZE, data.u_wave_num),
+                );
+                self.settings_updated = false;
+            }
 
-                if event.resize_args().is_some() {
-                    data.out_color = window.output_color.clone();
-                    data.out_depth = window.output_stencil.clone();
+            data.u_time_phase = self.time_phase;
+            data.u_model = model;
+            data.u_model_view_proj = model_view_projection(model, view, projection);
+            if let Some(pso_slice) = &self.pso_slice {
+                encoder.draw(&pso_slice.1, &pso_slice.0, data);
+            }
+        }
+
+        let settings = self.settings.upgrade().unwrap();
+        let settings = settings.borrow();
+        if settings.show_force {
+            if let Some(arrows) = &mut self.force_arrows {
+                if field_changed {
+                    arrows.update(
+                        factory,
+                        model,
+                        &self.sources.upgrade().unwrap().borrow(),
+                        &settings,
+                    );
                 }
+                arrows.render(encoder, view, projection);
             }
-        });
+        }
+        self.model_updated = false;
+        result
+    }
+
+    pub(crate) fn set_render_target(
+        &mut self,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) {
+        if let Some(arrows) = &mut self.force_arrows {
+            arrows.set_render_target(out_color.clone(), out_depth.clone());
+        }
+        if let Some(data) = &mut self.pipe_data {
+            data.out_color = out_color;
+            data.out_depth = out_depth;
+        }
     }
 
     fn update_phase_texture(
         data: &mut pipe::Data<gfx_device_gl::Resources>,
         factory: &mut gfx_device_gl::Factory,
         sources: &[SoundSource],
+        width: usize,
     ) {
-        use std::f32::consts::PI;
-
         let sampler_info = SamplerInfo::new(FilterMethod::Scale, WrapMode::Tile);
-        let mut texels = Vec::with_capacity(sources.len());
-        for source in sources {
-            texels.push([(source.phase / (2.0 * PI) * 255.) as u8, 0x00, 0x00, 0x00]);
-        }
-        let (_, texture_view) = factory
-            .create_texture_immutable::<format::Rgba8>(
-                Kind::D1(sources.len() as u16),
-                Mipmap::Provided,
-                &[&texels],
-            )
-            .unwrap();
+        let texels: Vec<[f32; 4]> = sources
+            .iter()
+            .map(|source| [source.phase, 0., 0., 0.])
+            .collect();
+        let texture_view = AcousticFiledSliceViewer::create_float_texture(factory, &texels, width);
         data.u_trans_phase = (texture_view, factory.create_sampler(sampler_info));
     }
 
+    fn update_amp_texture(
+        data: &mut pipe::Data<gfx_device_gl::Resources>,
+        factory: &mut gfx_device_gl::Factory,
+        sources: &[SoundSource],
+        width: usize,
+    ) {
+        let sampler_info = SamplerInfo::new(FilterMethod::Scale, WrapMode::Tile);
+        let texels: Vec<[f32; 4]> = sources
+            .iter()
+            .map(|source| [source.amp, 0., 0., 0.])
+            .collect();
+        let texture_view = AcousticFiledSliceViewer::create_float_texture(factory, &texels, width);
+        data.u_trans_amp = (texture_view, factory.create_sampler(sampler_info));
+    }
+
     fn update_color_map_texture(
         data: &mut pipe::Data<gfx_device_gl::Resources>,
         factory: &mut gfx_device_gl::Factory,
@@ -272,94 +542,130 @@ impl AcousticFiledSliceViewer {
         data: &mut pipe::Data<gfx_device_gl::Resources>,
         factory: &mut gfx_device_gl::Factory,
         sources: &[SoundSource],
-        source_size: f32,
+        width: usize,
     ) {
-        use format::Rgba8;
-
         let sampler_info = SamplerInfo::new(FilterMethod::Scale, WrapMode::Tile);
-        let size = sources.len();
-        let kind = Kind::D1(size as u16);
-        let mipmap = Mipmap::Provided;
-
-        let texels: Vec<[u8; 4]> = sources
+        let texels: Vec<[f32; 4]> = sources
             .iter()
-            .map(|source| {
-                let pos = vec_utils::to_vec4(source.pos);
-                vec_utils::vec4_map(pos, |p| ((p / source_size).round() as u16 % 256) as u8)
-            })
+            .map(|source| vec_utils::to_vec4(source.pos))
             .collect();
-        let (_, texture_view) = factory
-            .create_texture_immutable::<Rgba8>(kind, mipmap, &[&texels])
-            .unwrap();
+        let texture_view = AcousticFiledSliceViewer::create_float_texture(factory, &texels, width);
         data.u_trans_pos = (texture_view, factory.create_sampler(sampler_info));
+    }
 
-        let texels: Vec<[u8; 4]> = sources
+    fn update_direction_texture(
+        data: &mut pipe::Data<gfx_device_gl::Resources>,
+        factory: &mut gfx_device_gl::Factory,
+        sources: &[SoundSource],
+        width: usize,
+    ) {
+        let sampler_info = SamplerInfo::new(FilterMethod::Scale, WrapMode::Tile);
+        let texels: Vec<[f32; 4]> = sources
             .iter()
-            .map(|source| {
-                let pos = vec_utils::to_vec4(source.pos);
-                vec_utils::vec4_map(pos, |p| ((p / source_size).round() as u16 / 256) as u8)
-            })
+            .map(|source| vec_utils::to_vec4(vecmath::vec3_normalized(source.dir)))
             .collect();
-        let (_, texture_view) = factory
-            .create_texture_immutable::<Rgba8>(kind, mipmap, &[&texels])
-            .unwrap();
-        data.u_trans_pos_256 = (texture_view, factory.create_sampler(sampler_info));
+        let texture_view = AcousticFiledSliceViewer::create_float_texture(factory, &texels, width);
+        data.u_trans_dir = (texture_view, factory.create_sampler(sampler_info));
+    }
 
-        let texels: Vec<[u8; 4]> = sources
+    /// Uploads the signed gains as 32-bit floats, interpolated linearly by the sampler.
+    fn update_directivity_texture(
+        data: &mut pipe::Data<gfx_device_gl::Resources>,
+        factory: &mut gfx_device_gl::Factory,
+        table: &[f32],
+    ) {
+        let sampler_info = SamplerInfo::new(FilterMethod::Bilinear, WrapMode::Clamp);
+        let texels: Vec<[u32; 4]> = table
             .iter()
-            .map(|source| {
-                let pos = vec_utils::to_vec4(source.pos);
-                vec_utils::vec4_map(pos, |p| {
-                    (((p % source_size) / source_size * 256.0).round() as u16 % 256) as u8
-                })
-            })
+            .map(|&g| vec_utils::vec4_map([g, 0., 0., 0.], f32::to_bits))
             .collect();
         let (_, texture_view) = factory
-            .create_texture_immutable::<Rgba8>(kind, mipmap, &[&texels])
+            .create_texture_immutable::<format::Rgba32F>(
+                Kind::D1(table.len() as u16),
+                Mipmap::Provided,
+                &[&texels],
+            )
             .unwrap();
-        data.u_trans_pos_sub = (texture_view, factory.create_sampler(sampler_info));
+        data.u_directivity = (texture_view, factory.create_sampler(sampler_info));
     }
 
     fn initialize_pipe_data(
         &mut self,
         factory: &mut gfx_device_gl::Factory,
         vertex_buffer: Buffer<Resources, Vertex>,
-        phase_view: ShaderResourceView<Resources, [f32; 4]>,
         out_color: RenderTargetView<Resources, (format::R8_G8_B8_A8, format::Srgb)>,
         out_depth: DepthStencilView<Resources, (format::D24_S8, format::Unorm)>,
     ) {
         let sampler_info = SamplerInfo::new(FilterMethod::Scale, WrapMode::Tile);
         let len = self.sources.upgrade().unwrap().borrow().len();
-        let source_size = self.settings.upgrade().unwrap().borrow().source_size;
+        let wave_num = self.settings.upgrade().unwrap().borrow().wave_num();
         self.pipe_data = Some(pipe::Data {
             vertex_buffer,
             u_model_view_proj: [[0.; 4]; 4],
             u_model: vecmath::mat4_id(),
             u_color_scale: 1.0,
-            u_trans_size: source_size,
+            u_wave_num: wave_num,
+            u_attenuation: 0.0,
+            u_quantity: 0,
+            u_spl_reference: 1.0,
+            u_gorkov_k1: 0.0,
+            u_gorkov_k2: 0.0,
+            u_time_phase: 0.0,
             u_trans_num: len as f32,
+            // Replaced by `update_color_map_texture` before the first draw.
             u_color_map: (
-                AcousticFiledSliceViewer::generate_empty_view(factory, len),
+                AcousticFiledSliceViewer::generate_empty_view(factory, 1),
                 factory.create_sampler(SamplerInfo::new(FilterMethod::Bilinear, WrapMode::Clamp)),
             ),
             u_trans_pos: (
-                AcousticFiledSliceViewer::generate_empty_view(factory, len),
+                AcousticFiledSliceViewer::create_float_texture(factory, &[], 1),
                 factory.create_sampler(sampler_info),
             ),
-            u_trans_pos_256: (
-                AcousticFiledSliceViewer::generate_empty_view(factory, len),
+            u_trans_phase: (
+                AcousticFiledSliceViewer::create_float_texture(factory, &[], 1),
                 factory.create_sampler(sampler_info),
             ),
-            u_trans_pos_sub: (
-                AcousticFiledSliceViewer::generate_empty_view(factory, len),
+            u_trans_amp: (
+                AcousticFiledSliceViewer::create_float_texture(factory, &[], 1),
                 factory.create_sampler(sampler_info),
             ),
-            u_trans_phase: (phase_view, factory.create_sampler(sampler_info)),
+            u_trans_dir: (
+                AcousticFiledSliceViewer::create_float_texture(factory, &[], 1),
+                factory.create_sampler(sampler_info),
+            ),
+            u_directivity: (
+                AcousticFiledSliceViewer::generate_empty_view(factory, DIRECTIVITY_TABLE_SIZE),
+                factory.create_sampler(SamplerInfo::new(FilterMethod::Bilinear, WrapMode::Clamp)),
+            ),
             out_color,
             out_depth,
         });
     }
 
+    // Per-source data is laid out row by row in a 2D texture of the given width,
+    // since 1D textures are limited to a few thousand texels on most drivers.
+    fn create_float_texture(
+        factory: &mut gfx_device_gl::Factory,
+        texels: &[[f32; 4]],
+        width: usize,
+    ) -> ShaderResourceView<Resources, [f32; 4]> {
+        let width = width.min(texels.len()).max(1);
+        let height = texels.len().div_ceil(width).max(1);
+        let mut texels: Vec<[u32; 4]> = texels
+            .iter()
+            .map(|&texel| vec_utils::vec4_map(texel, f32::to_bits))
+            .collect();
+        texels.resize(width * height, [0; 4]);
+        let (_, view) = factory
+            .create_texture_immutable::<format::Rgba32F>(
+                Kind::D2(width as u16, height as u16, AaMode::Single),
+                Mipmap::Provided,
+                &[&texels],
+            )
+            .unwrap();
+        view
+    }
+
     fn generate_empty_view(
         factory: &mut gfx_device_gl::Factory,
         size: usize,
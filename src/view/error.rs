@@ -0,0 +1,70 @@
+/*
+ * File: error.rs
+ * Project: view
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ViewError {
+    TooManySources {
+        num: usize,
+        max: usize,
+    },
+    CaptureTooLarge {
+        width: u32,
+        height: u32,
+        scale: u16,
+        max: usize,
+    },
+    CaptureEmpty {
+        width: u32,
+        height: u32,
+    },
+    Graphics(String),
+    Image(image::ImageError),
+}
+
+impl fmt::Display for ViewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ViewError::TooManySources { num, max } => write!(
+                f,
+                "{} sound sources exceed the maximum of {} supported by the graphics driver",
+                num, max
+            ),
+            ViewError::CaptureTooLarge {
+                width,
+                height,
+                scale,
+                max,
+            } => write!(
+                f,
+                "{}x{} scaled by {} exceeds the maximum texture size of {}",
+                width, height, scale, max
+            ),
+            ViewError::CaptureEmpty { width, height } => {
+                write!(f, "cannot capture a {}x{} window", width, height)
+            }
+            ViewError::Graphics(msg) => write!(f, "graphics error: {}", msg),
+            ViewError::Image(e) => write!(f, "failed to write image: {}", e),
+        }
+    }
+}
+
+impl Error for ViewError {}
+
+impl From<image::ImageError> for ViewError {
+    fn from(e: image::ImageError) -> Self {
+        ViewError::Image(e)
+    }
+}
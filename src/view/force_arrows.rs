@@ -0,0 +1,180 @@
+/*
+ * File: force_arrows.rs
+ * Project: view
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+extern crate gfx;
+
+use camera_controllers::model_view_projection;
+use gfx::format;
+use gfx::handle::{DepthStencilView, RenderTargetView};
+use gfx::preset::depth;
+use gfx::state::Rasterizer;
+use gfx::traits::*;
+use gfx::{DepthTarget, Global, PipelineState, Primitive, RenderTarget, Slice, VertexBuffer};
+use gfx_device_gl::Resources;
+use piston_window::*;
+use shader_version::glsl::GLSL;
+use shader_version::Shaders;
+
+use crate::field::calc_radiation_force;
+use crate::sound_source::SoundSource;
+use crate::vec_utils;
+use crate::vec_utils::{Matrix4, Vector3};
+use crate::view::ViewerSettings;
+
+const ARROW_GRID_SIZE: usize = 16;
+const ARROW_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+
+gfx_vertex_struct!(Vertex {
+    a_pos: [f32; 3] = "a_pos",
+});
+
+gfx_pipeline!( pipe {
+    vertex_buffer: VertexBuffer<Vertex> = (),
+    u_view_proj: Global<[[f32; 4]; 4]> = "u_view_proj",
+    u_color: Global<[f32; 4]> = "u_color",
+    out_color: RenderTarget<format::Srgba8> = "o_Color",
+    out_depth: DepthTarget<format::DepthStencil> = depth::LESS_EQUAL_WRITE,
+});
+
+/// Radiation force on `ViewerSettings::particle`, drawn as arrows on a grid over a slice.
+/// Arrow lengths are normalized by the largest force on the grid.
+pub(crate) struct ForceArrows {
+    pipe_data: pipe::Data<Resources>,
+    pso_slice: (PipelineState<Resources, pipe::Meta>, Slice<Resources>),
+}
+
+impl ForceArrows {
+    pub(crate) fn new(
+        factory: &mut gfx_device_gl::Factory,
+        version: GLSL,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) -> ForceArrows {
+        let shader_set = factory
+            .create_shader_set(
+                Shaders::new()
+                    .set(GLSL::V1_50, include_str!("../../assets/shaders/solid.vert"))
+                    .get(version)
+                    .unwrap()
+                    .as_bytes(),
+                Shaders::new()
+                    .set(GLSL::V1_50, include_str!("../../assets/shaders/solid.frag"))
+                    .get(version)
+                    .unwrap()
+                    .as_bytes(),
+            )
+            .unwrap();
+        let pso = factory
+            .create_pipeline_state(
+                &shader_set,
+                Primitive::LineList,
+                Rasterizer::new_fill(),
+                pipe::new(),
+            )
+            .unwrap();
+        let vertex_buffer = factory.create_vertex_buffer(&[Vertex { a_pos: [0.; 3] }; 2]);
+        let slice = Slice::new_match_vertex_buffer(&vertex_buffer);
+        ForceArrows {
+            pipe_data: pipe::Data {
+                vertex_buffer,
+                u_view_proj: [[0.; 4]; 4],
+                u_color: ARROW_COLOR,
+                out_color,
+                out_depth,
+            },
+            pso_slice: (pso, slice),
+        }
+    }
+
+    /// Recomputes the arrows over the slice quad transformed by `model`.
+    pub(crate) fn update(
+        &mut self,
+        factory: &mut gfx_device_gl::Factory,
+        model: Matrix4,
+        sources: &[SoundSource],
+        settings: &ViewerSettings,
+    ) {
+        let n = ARROW_GRID_SIZE;
+        let points: Vec<Vector3> = (0..n * n)
+            .map(|i| {
+                let u = -1. + (2 * (i % n) + 1) as f32 / n as f32;
+                let v = -1. + (2 * (i / n) + 1) as f32 / n as f32;
+                let p = vecmath::col_mat4_transform(model, [u, 0., v, 1.]);
+                [p[0], p[1], p[2]]
+            })
+            .collect();
+        let forces: Vec<Vector3> = points
+            .iter()
+            .map(|&p| calc_radiation_force(p, sources, &settings.particle, settings))
+            .collect();
+        let max = forces
+            .iter()
+            .fold(0f32, |acc, &f| acc.max(vecmath::vec3_len(f)));
+
+        let normal = vecmath::col_mat4_transform(model, [0., 1., 0., 0.]);
+        let normal = vecmath::vec3_normalized([normal[0], normal[1], normal[2]]);
+        let half_width = vecmath::vec3_len([model[0][0], model[0][1], model[0][2]]);
+        let half_height = vecmath::vec3_len([model[2][0], model[2][1], model[2][2]]);
+        let cell = 2. * half_width.min(half_height) / n as f32;
+
+        let mut vertices = Vec::with_capacity(points.len() * 6);
+        if max > 0. {
+            for (&p, &f) in points.iter().zip(forces.iter()) {
+                let len = 0.8 * cell * vecmath::vec3_len(f) / max;
+                if !vec_utils::vec3_is_valid(f) || len < 1e-3 * cell {
+                    continue;
+                }
+                let dir = vecmath::vec3_normalized(f);
+                // Forces normal to the slice have no in-plane side; any perpendicular will do.
+                let side = vecmath::vec3_cross(normal, dir);
+                let side = if vecmath::vec3_len(side) > 1e-6 {
+                    vecmath::vec3_normalized(side)
+                } else {
+                    vec_utils::orthonormal_basis(dir).0
+                };
+                let tip = vecmath::vec3_add(p, vecmath::vec3_scale(dir, len / 2.));
+                let tail = vecmath::vec3_sub(p, vecmath::vec3_scale(dir, len / 2.));
+                let back = vecmath::vec3_sub(tip, vecmath::vec3_scale(dir, 0.3 * len));
+                for &head in &[
+                    vecmath::vec3_add(back, vecmath::vec3_scale(side, 0.15 * len)),
+                    vecmath::vec3_sub(back, vecmath::vec3_scale(side, 0.15 * len)),
+                ] {
+                    vertices.push(Vertex { a_pos: tip });
+                    vertices.push(Vertex { a_pos: head });
+                }
+                vertices.push(Vertex { a_pos: tail });
+                vertices.push(Vertex { a_pos: tip });
+            }
+        }
+        if vertices.is_empty() {
+            vertices.resize(2, Vertex { a_pos: [0.; 3] });
+        }
+        let vertex_buffer = factory.create_vertex_buffer(&vertices);
+        self.pso_slice.1 = Slice::new_match_vertex_buffer(&vertex_buffer);
+        self.pipe_data.vertex_buffer = vertex_buffer;
+    }
+
+    pub(crate) fn render(&mut self, encoder: &mut GfxEncoder, view: Matrix4, projection: Matrix4) {
+        self.pipe_data.u_view_proj = model_view_projection(vecmath::mat4_id(), view, projection);
+        encoder.draw(&self.pso_slice.1, &self.pso_slice.0, &self.pipe_data);
+    }
+
+    pub(crate) fn set_render_target(
+        &mut self,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) {
+        self.pipe_data.out_color = out_color;
+        self.pipe_data.out_depth = out_depth;
+    }
+}
@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 28/04/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -12,12 +12,18 @@
  */
 
 mod acoustic_field_slice_viewer;
+mod error;
 pub mod event;
+mod force_arrows;
+mod offscreen;
+mod reflector_planes;
 mod setting;
 mod sound_source_viewer;
 mod window;
 
 pub use acoustic_field_slice_viewer::AcousticFiledSliceViewer;
+pub use error::ViewError;
+pub use offscreen::OffscreenRenderer;
 pub use setting::ViewerSettings;
 pub use sound_source_viewer::SoundSourceViewer;
 pub use window::UpdateHandler;
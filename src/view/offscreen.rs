@@ -0,0 +1,284 @@
+/*
+ * File: offscreen.rs
+ * Project: view
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+use std::path::Path;
+
+use gfx::format;
+use gfx::handle::{Buffer, DepthStencilView, RenderTargetView, Texture};
+use gfx::memory::{Bind, Typed, Usage};
+use gfx::texture::{AaMode, Kind};
+use gfx::traits::*;
+use gfx::Device as _;
+use gfx::Factory as _;
+use gfx_device_gl::{Device, Factory, Resources};
+use glutin::GlContext;
+use image::RgbaImage;
+use piston_window::{GfxEncoder, OpenGL};
+
+use crate::sound_source::SoundSource;
+use crate::vec_utils::Matrix4;
+use crate::view::window::perspective;
+use crate::view::{
+    AcousticFiledSliceViewer, SoundSourceViewer, UpdateHandler, ViewError, ViewerSettings,
+};
+
+/// Color and depth buffers that can be read back into an image.
+pub(crate) struct RenderTarget {
+    pub(crate) width: u16,
+    pub(crate) height: u16,
+    pub(crate) out_color: RenderTargetView<Resources, format::Srgba8>,
+    pub(crate) out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    color: Texture<Resources, format::R8_G8_B8_A8>,
+    download: Buffer<Resources, [u8; 4]>,
+}
+
+impl RenderTarget {
+    pub(crate) fn new(factory: &mut Factory, width: u16, height: u16) -> Result<Self, ViewError> {
+        let color = factory
+            .create_texture::<format::R8_G8_B8_A8>(
+                Kind::D2(width, height, AaMode::Single),
+                1,
+                Bind::RENDER_TARGET | Bind::TRANSFER_SRC,
+                Usage::Data,
+                Some(format::ChannelType::Srgb),
+            )
+            .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        let out_color = factory
+            .view_texture_as_render_target(&color, 0, None)
+            .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        let out_depth = factory
+            .create_depth_stencil_view_only(width, height)
+            .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        let download = factory
+            .create_download_buffer::<[u8; 4]>(width as usize * height as usize)
+            .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        Ok(RenderTarget {
+            width,
+            height,
+            out_color,
+            out_depth,
+            color,
+            download,
+        })
+    }
+
+    pub(crate) fn projection(&self) -> Matrix4 {
+        perspective(self.width as f32, self.height as f32)
+    }
+
+    pub(crate) fn clear(&self, encoder: &mut GfxEncoder) {
+        encoder.clear(&self.out_color, [0.3, 0.3, 0.3, 1.0]);
+        encoder.clear_depth(&self.out_depth, 1.0);
+    }
+
+    /// Flushes `encoder` and copies the color buffer into an image.
+    pub(crate) fn read(
+        &self,
+        encoder: &mut GfxEncoder,
+        device: &mut Device,
+        factory: &mut Factory,
+    ) -> Result<RgbaImage, ViewError> {
+        let info = self
+            .color
+            .get_info()
+            .to_raw_image_info(format::ChannelType::Srgb, 0);
+        encoder
+            .copy_texture_to_buffer_raw(self.color.raw(), None, info, self.download.raw(), 0)
+            .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        encoder.flush(device);
+
+        let pixels: Vec<u8> = {
+            let reader = factory
+                .read_mapping(&self.download)
+                .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+            reader.iter().flat_map(|p| p.iter().cloned()).collect()
+        };
+
+        // OpenGL stores rows from bottom to top.
+        let image = RgbaImage::from_raw(self.width as u32, self.height as u32, pixels).unwrap();
+        Ok(image::imageops::flip_vertical(&image))
+    }
+}
+
+/// Averages each `scale` x `scale` block of `image` into one pixel.
+///
+/// The color channels are averaged in linear light, since the pixels are sRGB encoded.
+pub(crate) fn downsample(image: &RgbaImage, scale: u32) -> RgbaImage {
+    if scale <= 1 {
+        return image.clone();
+    }
+    let samples = (scale * scale) as f32;
+    RgbaImage::from_fn(image.width() / scale, image.height() / scale, |x, y| {
+        let mut sum = [0f32; 4];
+        for dy in 0..scale {
+            for dx in 0..scale {
+                let pixel = image.get_pixel(x * scale + dx, y * scale + dy);
+                for (s, &c) in sum.iter_mut().zip(pixel.0.iter()).take(3) {
+                    *s += srgb_to_linear(c);
+                }
+                sum[3] += pixel.0[3] as f32 / 255.;
+            }
+        }
+        image::Rgba([
+            linear_to_srgb(sum[0] / samples),
+            linear_to_srgb(sum[1] / samples),
+            linear_to_srgb(sum[2] / samples),
+            (sum[3] / samples * 255.).round() as u8,
+        ])
+    })
+}
+
+fn srgb_to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.;
+    if c <= 0.040_45 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> u8 {
+    let c = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1. / 2.4) - 0.055
+    };
+    (c * 255.).round().clamp(0., 255.) as u8
+}
+
+/// Renders the same scene as `ViewWindow` into an off-screen framebuffer.
+///
+/// No window is shown, but glutin still needs a display connection to create the GL context:
+/// on Linux without a display, run under Xvfb (e.g. `xvfb-run`).
+/// `LIBGL_ALWAYS_SOFTWARE=1` selects Mesa's software rasterizer on machines without a GPU.
+pub struct OffscreenRenderer {
+    pub update_handler: UpdateHandler,
+    device: Device,
+    factory: Factory,
+    encoder: GfxEncoder,
+    target: RenderTarget,
+    _context: glutin::Context,
+    _events_loop: glutin::EventsLoop,
+}
+
+impl OffscreenRenderer {
+    pub fn new(
+        sources: Vec<SoundSource>,
+        sound_source_viewer: SoundSourceViewer,
+        field_slice_viewer: AcousticFiledSliceViewer,
+        settings: ViewerSettings,
+        size: [u16; 2],
+    ) -> Result<OffscreenRenderer, ViewError> {
+        let opengl = OpenGL::V3_2;
+
+        let events_loop = glutin::EventsLoop::new();
+        let context_builder = glutin::ContextBuilder::new()
+            .with_gl(glutin::GlRequest::Specific(glutin::Api::OpenGl, (3, 2)))
+            .with_gl_profile(glutin::GlProfile::Core);
+        let context = glutin::Context::new(&events_loop, context_builder, false)
+            .map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        unsafe { context.make_current() }.map_err(|e| ViewError::Graphics(format!("{:?}", e)))?;
+        let (device, mut factory) =
+            gfx_device_gl::create(|s| context.get_proc_address(s) as *const std::os::raw::c_void);
+        let encoder: GfxEncoder = factory.create_command_buffer().into();
+
+        let target = RenderTarget::new(&mut factory, size[0], size[1])?;
+
+        let mut update_handler = UpdateHandler::new(
+            sources,
+            sound_source_viewer,
+            field_slice_viewer,
+            settings,
+            UpdateHandler::default_camera(),
+        );
+        update_handler
+            .sound_source_viewer
+            .render_setting_with_target(
+                &mut factory,
+                opengl,
+                target.out_color.clone(),
+                target.out_depth.clone(),
+            );
+        update_handler.set_render_target(target.out_color.clone(), target.out_depth.clone());
+        update_handler.initialize_viewers(&mut factory)?;
+
+        Ok(OffscreenRenderer {
+            update_handler,
+            device,
+            factory,
+            encoder,
+            target,
+            _context: context,
+            _events_loop: events_loop,
+        })
+    }
+
+    pub fn render(&mut self) -> Result<RgbaImage, ViewError> {
+        self.update_handler.update_sources();
+        self.update_handler.initialize_viewers(&mut self.factory)?;
+
+        self.target.clear(&mut self.encoder);
+        self.update_handler.render(
+            &mut self.encoder,
+            &mut self.factory,
+            self.target.projection(),
+        )?;
+        let image = self
+            .target
+            .read(&mut self.encoder, &mut self.device, &mut self.factory)?;
+        self.device.cleanup();
+        Ok(image)
+    }
+
+    pub fn render_to_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ViewError> {
+        self.render()?.save(path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srgb_round_trip() {
+        for c in 0..=255u8 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(c)), c);
+        }
+    }
+
+    #[test]
+    fn downsample_averages_blocks() {
+        let image = RgbaImage::from_fn(4, 2, |x, _| {
+            if x < 2 {
+                image::Rgba([200, 100, 50, 255])
+            } else if x % 2 == 0 {
+                image::Rgba([0, 0, 0, 0])
+            } else {
+                image::Rgba([255, 255, 255, 255])
+            }
+        });
+        let small = downsample(&image, 2);
+        assert_eq!(small.dimensions(), (2, 1));
+        // A uniform block keeps its color.
+        assert_eq!(small.get_pixel(0, 0).0, [200, 100, 50, 255]);
+        // Half black and half white is 0.5 in linear light.
+        assert_eq!(small.get_pixel(1, 0).0, [188, 188, 188, 128]);
+    }
+
+    #[test]
+    fn downsample_by_one_is_identity() {
+        let image = RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 7, 255]));
+        assert_eq!(downsample(&image, 1), image);
+    }
+}
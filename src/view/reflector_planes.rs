@@ -0,0 +1,151 @@
+/*
+ * File: reflector_planes.rs
+ * Project: view
+ * Created Date: 18/10/2026
+ * Author: Shun Suzuki
+ * -----
+ * Last Modified: 18/10/2026
+ * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
+ * -----
+ * Copyright (c) 2026 Hapis Lab. All rights reserved.
+ *
+ */
+
+extern crate gfx;
+
+use camera_controllers::model_view_projection;
+use gfx::format;
+use gfx::handle::{DepthStencilView, RenderTargetView};
+use gfx::preset::depth;
+use gfx::state::{Blend, ColorMask, Rasterizer};
+use gfx::traits::*;
+use gfx::{BlendTarget, DepthTarget, Global, PipelineState, Primitive, Slice, VertexBuffer};
+use gfx_device_gl::Resources;
+use piston_window::*;
+use shader_version::glsl::GLSL;
+use shader_version::Shaders;
+
+use crate::field::Reflector;
+use crate::vec_utils;
+use crate::vec_utils::Matrix4;
+
+const PLANE_COLOR: [f32; 4] = [0.6, 0.8, 1.0, 0.3];
+
+gfx_vertex_struct!(Vertex {
+    a_pos: [f32; 3] = "a_pos",
+});
+
+fn alpha_blender() -> Blend {
+    use gfx::state::{BlendValue, Equation, Factor};
+    Blend::new(
+        Equation::Add,
+        Factor::ZeroPlus(BlendValue::SourceAlpha),
+        Factor::OneMinus(BlendValue::SourceAlpha),
+    )
+}
+
+gfx_pipeline!( pipe {
+    vertex_buffer: VertexBuffer<Vertex> = (),
+    u_view_proj: Global<[[f32; 4]; 4]> = "u_view_proj",
+    u_color: Global<[f32; 4]> = "u_color",
+    out_color: BlendTarget<format::Srgba8> = ("o_Color", ColorMask::all(), alpha_blender()),
+    out_depth: DepthTarget<format::DepthStencil> = depth::LESS_EQUAL_TEST,
+});
+
+/// `ViewerSettings::reflectors`, drawn as translucent squares.
+pub(crate) struct ReflectorPlanes {
+    pipe_data: pipe::Data<Resources>,
+    pso_slice: (PipelineState<Resources, pipe::Meta>, Slice<Resources>),
+}
+
+impl ReflectorPlanes {
+    pub(crate) fn new(
+        factory: &mut gfx_device_gl::Factory,
+        version: GLSL,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) -> ReflectorPlanes {
+        let shader_set = factory
+            .create_shader_set(
+                Shaders::new()
+                    .set(GLSL::V1_50, include_str!("../../assets/shaders/solid.vert"))
+                    .get(version)
+                    .unwrap()
+                    .as_bytes(),
+                Shaders::new()
+                    .set(GLSL::V1_50, include_str!("../../assets/shaders/solid.frag"))
+                    .get(version)
+                    .unwrap()
+                    .as_bytes(),
+            )
+            .unwrap();
+        let pso = factory
+            .create_pipeline_state(
+                &shader_set,
+                Primitive::TriangleList,
+                Rasterizer::new_fill(),
+                pipe::new(),
+            )
+            .unwrap();
+        let vertex_buffer = factory.create_vertex_buffer(&[Vertex { a_pos: [0.; 3] }; 3]);
+        let slice = Slice::new_match_vertex_buffer(&vertex_buffer);
+        ReflectorPlanes {
+            pipe_data: pipe::Data {
+                vertex_buffer,
+                u_view_proj: [[0.; 4]; 4],
+                u_color: PLANE_COLOR,
+                out_color,
+                out_depth,
+            },
+            pso_slice: (pso, slice),
+        }
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        factory: &mut gfx_device_gl::Factory,
+        reflectors: &[Reflector],
+    ) {
+        let mut vertices = Vec::with_capacity(reflectors.len() * 6);
+        for reflector in reflectors {
+            let (u, v) = vec_utils::orthonormal_basis(reflector.normal);
+            let u = vecmath::vec3_scale(u, reflector.extent);
+            let v = vecmath::vec3_scale(v, reflector.extent);
+            let corner = |su: f32, sv: f32| Vertex {
+                a_pos: vecmath::vec3_add(
+                    reflector.pos,
+                    vecmath::vec3_add(vecmath::vec3_scale(u, su), vecmath::vec3_scale(v, sv)),
+                ),
+            };
+            let quad = [
+                corner(-1., -1.),
+                corner(1., -1.),
+                corner(1., 1.),
+                corner(-1., 1.),
+            ];
+            for &i in &[0, 1, 2, 2, 3, 0] {
+                vertices.push(quad[i]);
+            }
+        }
+        if vertices.is_empty() {
+            vertices.resize(3, Vertex { a_pos: [0.; 3] });
+        }
+        let vertex_buffer = factory.create_vertex_buffer(&vertices);
+        self.pso_slice.1 = Slice::new_match_vertex_buffer(&vertex_buffer);
+        self.pipe_data.vertex_buffer = vertex_buffer;
+    }
+
+    pub(crate) fn render(&mut self, encoder: &mut GfxEncoder, view: Matrix4, projection: Matrix4) {
+        self.pipe_data.u_view_proj = model_view_projection(vecmath::mat4_id(), view, projection);
+        encoder.draw(&self.pso_slice.1, &self.pso_slice.0, &self.pipe_data);
+    }
+
+    pub(crate) fn set_render_target(
+        &mut self,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) {
+        self.pipe_data.out_color = out_color;
+        self.pipe_data.out_depth = out_depth;
+    }
+}
@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 28/04/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -12,17 +12,28 @@
  */
 
 use crate::common::coloring_method::ColoringMethod;
+use std::f32::consts::PI;
+
+use crate::field::{Directivity, FieldQuantity, Medium, Particle, Reflector};
 use scarlet::colormap::ListedColorMap;
 
 #[derive(Debug, Clone)]
 pub struct ViewerSettings {
     pub freqency: f32,
     pub source_size: f32,
-    pub wave_length: f32,
+    pub medium: Medium,
     pub trans_coloring: ColoringMethod,
     pub field_color_map: ListedColorMap,
     pub color_scale: f32,
     pub slice_alpha: f32,
+    pub directivity: Directivity,
+    pub field_quantity: FieldQuantity,
+    pub animate: bool,
+    pub time_scale: f32,
+    pub particle: Particle,
+    pub show_force: bool,
+    pub reflectors: Vec<Reflector>,
+    pub reflection_order: usize,
 }
 
 impl ViewerSettings {
@@ -35,11 +46,29 @@ impl ViewerSettings {
         ViewerSettings {
             freqency,
             source_size,
-            wave_length: 340e3 / freqency,
+            medium: Medium::default(),
             trans_coloring,
             field_color_map,
             color_scale: 1.0,
             slice_alpha: 1.0,
+            directivity: Directivity::Omni,
+            field_quantity: FieldQuantity::Amplitude,
+            animate: false,
+            time_scale: 1e-4,
+            particle: Particle::default(),
+            show_force: false,
+            reflectors: vec![],
+            reflection_order: 1,
         }
     }
+
+    /// Wavelength in mm of `freqency` in `medium`.
+    pub fn wave_length(&self) -> f32 {
+        self.medium.wave_length(self.freqency)
+    }
+
+    /// Wavenumber in rad/mm.
+    pub fn wave_num(&self) -> f32 {
+        2.0 * PI / self.wave_length()
+    }
 }
@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 29/04/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -29,6 +29,7 @@ use shader_version::glsl::GLSL;
 use shader_version::Shaders;
 
 use std::cell::RefCell;
+use std::ops::Range;
 use std::rc::Weak;
 
 use crate::sound_source::SoundSource;
@@ -76,8 +77,17 @@ pub struct SoundSourceViewer {
     models: Vec<Matrix4>,
     position_updated: bool,
     phase_updated: bool,
+    highlighted: Option<Range<usize>>,
     vertex_buffer: Option<Buffer<Resources, Vertex>>,
     view: Option<ShaderResourceView<Resources, [f32; 4]>>,
+    out_color: Option<RenderTargetView<Resources, format::Srgba8>>,
+    out_depth: Option<DepthStencilView<Resources, format::DepthStencil>>,
+}
+
+impl Default for SoundSourceViewer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SoundSourceViewer {
@@ -90,14 +100,30 @@ impl SoundSourceViewer {
             models: vec![],
             position_updated: true,
             phase_updated: true,
+            highlighted: None,
             vertex_buffer: None,
             view: None,
+            out_color: None,
+            out_depth: None,
         }
     }
 
     pub fn render_setting(&mut self, window: &PistonWindow, opengl: OpenGL) {
-        let factory = &mut window.factory.clone();
+        self.render_setting_with_target(
+            &mut window.factory.clone(),
+            opengl,
+            window.output_color.clone(),
+            window.output_stencil.clone(),
+        );
+    }
 
+    pub(crate) fn render_setting_with_target(
+        &mut self,
+        factory: &mut gfx_device_gl::Factory,
+        opengl: OpenGL,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) {
         let vertex_data = vec![
             Vertex::new([-1, -1, 0], [0, 0]),
             Vertex::new([1, -1, 0], [1, 0]),
@@ -122,16 +148,12 @@ impl SoundSourceViewer {
         )
         .unwrap();
 
-        self.vertex_buffer = Some(vertex_buffer.clone());
-        self.view = Some(circle.view.clone());
+        self.vertex_buffer = Some(vertex_buffer);
+        self.view = Some(circle.view);
+        self.out_color = Some(out_color);
+        self.out_depth = Some(out_depth);
 
-        self.initialize_pipe_data(
-            factory,
-            vertex_buffer,
-            circle.view,
-            window.output_color.clone(),
-            window.output_stencil.clone(),
-        );
+        self.initialize_pipe_data(factory);
 
         self.update_phase();
         self.update_position();
@@ -148,13 +170,14 @@ impl SoundSourceViewer {
             self.init_model();
         }
 
+        let s = 0.5 * self.settings.upgrade().unwrap().borrow().source_size;
         for (i, source) in self.sources.upgrade().unwrap().borrow().iter().enumerate() {
-            self.models[i][3][0] = source.pos[0];
-            self.models[i][3][1] = source.pos[1];
-            self.models[i][3][2] = source.pos[2];
+            // Rebuilt from scratch so that the rotation is not applied again on every call.
+            let mut model = vec_utils::mat4_scale(s);
+            model[3] = vec_utils::to_point4(source.pos);
             let rot = vec_utils::quaternion_to([0., 0., 1.], source.dir);
             let rotm = vec_utils::mat4_rot(rot);
-            self.models[i] = vecmath::col_mat4_mul(self.models[i], rotm);
+            self.models[i] = vecmath::col_mat4_mul(model, rotm);
         }
         self.position_updated = true;
     }
@@ -163,28 +186,47 @@ impl SoundSourceViewer {
         self.phase_updated = true;
     }
 
+    /// Dims every source outside `range` so that the sources in it stand out.
+    pub fn highlight(&mut self, range: Option<Range<usize>>) {
+        self.highlighted = range;
+        self.phase_updated = true;
+    }
+
     pub fn renderer(
         &mut self,
         window: &mut PistonWindow,
         event: &Event,
         view: Matrix4,
         projection: Matrix4,
+    ) {
+        self.render(&mut window.encoder, &mut window.factory, view, projection);
+        if event.resize_args().is_some() {
+            self.set_render_target(window.output_color.clone(), window.output_stencil.clone());
+        }
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        encoder: &mut GfxEncoder,
+        factory: &mut gfx_device_gl::Factory,
+        view: Matrix4,
+        projection: Matrix4,
     ) {
         if self.phase_updated {
             if self.pipe_data_list.len() != self.sources.upgrade().unwrap().borrow().len() {
-                let factory = &mut window.factory;
-                self.initialize_pipe_data(
-                    factory,
-                    self.vertex_buffer.clone().unwrap(),
-                    self.view.clone().unwrap(),
-                    window.output_color.clone(),
-                    window.output_stencil.clone(),
-                );
+                self.initialize_pipe_data(factory);
             }
 
             let coloring_method = self.settings.upgrade().unwrap().borrow().trans_coloring;
             for (i, source) in self.sources.upgrade().unwrap().borrow().iter().enumerate() {
-                self.pipe_data_list[i].i_color = coloring_method(source.phase / (2.0 * PI));
+                let mut color = coloring_method(source.phase / (2.0 * PI));
+                color[3] *= source.amp.clamp(0.0, 1.0);
+                if let Some(range) = &self.highlighted {
+                    if !range.contains(&i) {
+                        color[3] *= 0.2;
+                    }
+                }
+                self.pipe_data_list[i].i_color = color;
             }
         }
 
@@ -198,37 +240,37 @@ impl SoundSourceViewer {
 
         if let Some(pso_slice) = &self.pso_slice {
             for i in 0..self.pipe_data_list.len() {
-                window
-                    .encoder
-                    .draw(&pso_slice.1, &pso_slice.0, &self.pipe_data_list[i]);
-            }
-        }
-
-        if event.resize_args().is_some() {
-            for pipe_data in &mut self.pipe_data_list {
-                pipe_data.out_color = window.output_color.clone();
-                pipe_data.out_depth = window.output_stencil.clone();
+                encoder.draw(&pso_slice.1, &pso_slice.0, &self.pipe_data_list[i]);
             }
         }
     }
 
-    fn initialize_pipe_data(
+    pub(crate) fn set_render_target(
         &mut self,
-        factory: &mut gfx_device_gl::Factory,
-        vertex_buffer: Buffer<Resources, Vertex>,
-        view: ShaderResourceView<Resources, [f32; 4]>,
-        out_color: RenderTargetView<Resources, (format::R8_G8_B8_A8, format::Srgb)>,
-        out_depth: DepthStencilView<Resources, (format::D24_S8, format::Unorm)>,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
     ) {
+        for pipe_data in &mut self.pipe_data_list {
+            pipe_data.out_color = out_color.clone();
+            pipe_data.out_depth = out_depth.clone();
+        }
+        self.out_color = Some(out_color);
+        self.out_depth = Some(out_depth);
+    }
+
+    fn initialize_pipe_data(&mut self, factory: &mut gfx_device_gl::Factory) {
         let sampler_info = SamplerInfo::new(FilterMethod::Bilinear, WrapMode::Clamp);
         self.pipe_data_list = vec![
             pipe::Data {
-                vertex_buffer: vertex_buffer,
+                vertex_buffer: self.vertex_buffer.clone().unwrap(),
                 u_model_view_proj: [[0.; 4]; 4],
-                t_color: (view, factory.create_sampler(sampler_info)),
+                t_color: (
+                    self.view.clone().unwrap(),
+                    factory.create_sampler(sampler_info)
+                ),
                 i_color: [0., 0., 0., 1.],
-                out_color,
-                out_depth,
+                out_color: self.out_color.clone().unwrap(),
+                out_depth: self.out_depth.clone().unwrap(),
             };
             self.sources.upgrade().unwrap().borrow().len()
         ]
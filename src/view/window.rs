@@ -4,7 +4,7 @@
  * Created Date: 27/04/2020
  * Author: Shun Suzuki
  * -----
- * Last Modified: 01/05/2020
+ * Last Modified: 18/10/2026
  * Modified By: Shun Suzuki (suzuki@hapis.k.u-tokyo.ac.jp)
  * -----
  * Copyright (c) 2020 Hapis Lab. All rights reserved.
@@ -12,56 +12,123 @@
  */
 
 use std::cell::RefCell;
+use std::ops::Range;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use camera_controllers::{Camera, CameraPerspective, FirstPerson, FirstPersonSettings};
+use gfx::format;
+use gfx::handle::{DepthStencilView, RenderTargetView};
+use gfx::Factory as _;
+use gfx_device_gl::Resources;
 use piston_window::Window;
 use piston_window::*;
 
+use crate::geometry::Device;
 use crate::sound_source::SoundSource;
-use crate::vec_utils::Matrix4;
-use crate::view::{AcousticFiledSliceViewer, SoundSourceViewer, ViewerSettings};
+use crate::vec_utils::{Matrix4, Quaterion, Vector3};
+use crate::view::offscreen::{downsample, RenderTarget};
+use crate::view::reflector_planes::ReflectorPlanes;
+use crate::view::{AcousticFiledSliceViewer, SoundSourceViewer, ViewError, ViewerSettings};
 
 pub struct UpdateHandler {
     update_source_pos: bool,
     update_source_phase: bool,
+    update_settings: bool,
+    update_color_map: bool,
+    time_phase: f32,
+    capture: Option<u16>,
+    capture_result: Option<Result<PathBuf, ViewError>>,
+    devices: Vec<(Device, Range<usize>)>,
+    field_slice_viewers: Vec<AcousticFiledSliceViewer>,
+    reflector_planes: Option<ReflectorPlanes>,
+    reflectors_updated: bool,
+    out_color: Option<RenderTargetView<Resources, format::Srgba8>>,
+    out_depth: Option<DepthStencilView<Resources, format::DepthStencil>>,
     pub sound_source_viewer: SoundSourceViewer,
-    pub field_slice_viewer: AcousticFiledSliceViewer,
     pub sources: Rc<RefCell<Vec<SoundSource>>>,
     pub settings: Rc<RefCell<ViewerSettings>>,
     pub camera: Camera<f32>,
 }
 
 impl UpdateHandler {
-    fn new(
-        sources: Rc<RefCell<Vec<SoundSource>>>,
+    pub(crate) fn new(
+        sources: Vec<SoundSource>,
         sound_source_viewer: SoundSourceViewer,
         field_slice_viewer: AcousticFiledSliceViewer,
-        settings: Rc<RefCell<ViewerSettings>>,
+        settings: ViewerSettings,
         camera: Camera<f32>,
     ) -> UpdateHandler {
-        UpdateHandler {
+        let mut sound_source_viewer = sound_source_viewer;
+
+        let ref_sources = Rc::new(RefCell::new(sources));
+        sound_source_viewer.sources = Rc::downgrade(&ref_sources);
+
+        let ref_settings = Rc::new(RefCell::new(settings));
+        sound_source_viewer.settings = Rc::downgrade(&ref_settings);
+
+        sound_source_viewer.init_model();
+
+        let mut handler = UpdateHandler {
             update_source_phase: false,
             update_source_pos: false,
+            update_settings: false,
+            update_color_map: false,
+            time_phase: 0.,
+            capture: None,
+            capture_result: None,
+            devices: vec![],
+            field_slice_viewers: vec![],
+            reflector_planes: None,
+            reflectors_updated: true,
+            out_color: None,
+            out_depth: None,
             sound_source_viewer,
-            field_slice_viewer,
-            sources,
-            settings,
+            sources: ref_sources,
+            settings: ref_settings,
             camera,
-        }
+        };
+        handler.add_slice(field_slice_viewer);
+        handler
     }
 
-    fn update_sources(&mut self) {
+    pub(crate) fn default_camera() -> Camera<f32> {
+        let first_person =
+            FirstPerson::new([90., -250.0, 120.0], FirstPersonSettings::keyboard_wasd());
+        let mut camera = first_person.camera(0.);
+        camera.set_yaw_pitch(0., -std::f32::consts::PI / 2.0);
+        camera
+    }
+
+    pub(crate) fn update_sources(&mut self) {
         if self.update_source_phase {
             self.sound_source_viewer.update_phase();
-            self.field_slice_viewer.update_source_phase();
+            for viewer in &mut self.field_slice_viewers {
+                viewer.update_source_phase();
+            }
             self.update_source_phase = false;
         }
         if self.update_source_pos {
             self.sound_source_viewer.update_position();
-            self.field_slice_viewer.update_source_pos();
+            for viewer in &mut self.field_slice_viewers {
+                viewer.update_source_pos();
+            }
             self.update_source_pos = false;
         }
+        if self.update_settings {
+            for viewer in &mut self.field_slice_viewers {
+                viewer.update_settings();
+            }
+            self.reflectors_updated = true;
+            self.update_settings = false;
+        }
+        if self.update_color_map {
+            for viewer in &mut self.field_slice_viewers {
+                viewer.update_color_map();
+            }
+            self.update_color_map = false;
+        }
     }
 
     pub fn update_phase(&mut self) {
@@ -71,11 +138,220 @@ impl UpdateHandler {
     pub fn update_position(&mut self) {
         self.update_source_pos = true;
     }
+
+    pub fn update_settings(&mut self) {
+        self.update_settings = true;
+    }
+
+    /// Call after changing the color map, `color_scale` or `slice_alpha` in the settings.
+    pub fn update_color_map(&mut self) {
+        self.update_color_map = true;
+    }
+
+    /// Adds a slice to the view and returns its index.
+    /// The slice is drawn from the next frame on.
+    pub fn add_slice(&mut self, field_slice_viewer: AcousticFiledSliceViewer) -> usize {
+        let mut field_slice_viewer = field_slice_viewer;
+        field_slice_viewer.sources = Rc::downgrade(&self.sources);
+        field_slice_viewer.settings = Rc::downgrade(&self.settings);
+        field_slice_viewer.set_time_phase(self.time_phase);
+        self.field_slice_viewers.push(field_slice_viewer);
+        self.field_slice_viewers.len() - 1
+    }
+
+    /// Removes a slice from the view. The indices of the following slices are shifted down by one.
+    pub fn remove_slice(&mut self, idx: usize) -> AcousticFiledSliceViewer {
+        self.field_slice_viewers.remove(idx)
+    }
+
+    pub fn num_slices(&self) -> usize {
+        self.field_slice_viewers.len()
+    }
+
+    pub fn slice(&self, idx: usize) -> &AcousticFiledSliceViewer {
+        &self.field_slice_viewers[idx]
+    }
+
+    pub fn slice_mut(&mut self, idx: usize) -> &mut AcousticFiledSliceViewer {
+        &mut self.field_slice_viewers[idx]
+    }
+
+    /// Creates the GPU resources of the reflector planes and of the slices added since the last call.
+    /// A slice that cannot be initialized is removed.
+    pub(crate) fn initialize_viewers(
+        &mut self,
+        factory: &mut gfx_device_gl::Factory,
+    ) -> Result<(), ViewError> {
+        let (out_color, out_depth) = match (&self.out_color, &self.out_depth) {
+            (Some(out_color), Some(out_depth)) => (out_color.clone(), out_depth.clone()),
+            _ => return Ok(()),
+        };
+        if self.reflector_planes.is_none() {
+            self.reflector_planes = Some(ReflectorPlanes::new(
+                factory,
+                OpenGL::V3_2.to_glsl(),
+                out_color.clone(),
+                out_depth.clone(),
+            ));
+        }
+        let mut i = 0;
+        while i < self.field_slice_viewers.len() {
+            let viewer = &mut self.field_slice_viewers[i];
+            if !viewer.is_initialized() {
+                if let Err(err) = viewer.render_setting_with_target(
+                    factory,
+                    OpenGL::V3_2,
+                    out_color.clone(),
+                    out_depth.clone(),
+                ) {
+                    self.field_slice_viewers.remove(i);
+                    return Err(err);
+                }
+            }
+            i += 1;
+        }
+        Ok(())
+    }
+
+    /// Advances `omega * t` by `dt` seconds slowed down by `time_scale`.
+    pub(crate) fn advance_time(&mut self, dt: f64) {
+        let phase = {
+            let settings = self.settings.borrow();
+            let omega = 2.0 * std::f64::consts::PI * settings.freqency as f64;
+            self.time_phase as f64 + omega * dt * settings.time_scale as f64
+        };
+        self.time_phase = (phase % (2.0 * std::f64::consts::PI)) as f32;
+        for viewer in &mut self.field_slice_viewers {
+            viewer.set_time_phase(self.time_phase);
+        }
+    }
+
+    /// Groups the sources into `devices`, which must be in the order their sources were added.
+    pub(crate) fn set_devices(&mut self, devices: Vec<Device>) {
+        let mut offset = 0;
+        self.devices = devices
+            .into_iter()
+            .map(|device| {
+                let range = offset..offset + device.len();
+                offset = range.end;
+                (device, range)
+            })
+            .collect();
+    }
+
+    pub fn num_devices(&self) -> usize {
+        self.devices.len()
+    }
+
+    pub fn device(&self, idx: usize) -> &Device {
+        &self.devices[idx].0
+    }
+
+    /// Moves the sources of a device to a new pose, keeping their phases and amplitudes.
+    pub fn set_device_pose(&mut self, idx: usize, translation: Vector3, rotation: Quaterion) {
+        let (device, range) = &mut self.devices[idx];
+        device.translation = translation;
+        device.rotation = rotation;
+        device.apply_pose(&mut self.sources.borrow_mut()[range.clone()]);
+        self.update_position();
+    }
+
+    pub fn translate_device(&mut self, idx: usize, travel: Vector3) {
+        let device = self.device(idx);
+        let translation = vecmath::vec3_add(device.translation, travel);
+        let rotation = device.rotation;
+        self.set_device_pose(idx, translation, rotation);
+    }
+
+    /// Rotates a device about its own origin around the world `axis`.
+    pub fn rotate_device(&mut self, idx: usize, axis: Vector3, rot: f32) {
+        let device = self.device(idx);
+        let translation = device.translation;
+        let rotation = quaternion::mul(quaternion::axis_angle(axis, rot), device.rotation);
+        self.set_device_pose(idx, translation, rotation);
+    }
+
+    /// Highlights the sources of a device in the sound source view, or clears the highlight with `None`.
+    pub fn highlight_device(&mut self, idx: Option<usize>) {
+        let range = idx.map(|i| self.devices[i].1.clone());
+        self.sound_source_viewer.highlight(range);
+    }
+
+    /// Saves the next frame to a timestamped PNG in the working directory.
+    pub fn capture(&mut self) {
+        self.capture_supersampled(1);
+    }
+
+    /// Same as `capture`, but renders at `scale` times the window resolution
+    /// and box-filters the result back down to the window size.
+    pub fn capture_supersampled(&mut self, scale: u16) {
+        self.capture = Some(scale.max(1));
+    }
+
+    /// Path of the last saved capture or the error that prevented it,
+    /// available from the update after the frame is rendered.
+    pub fn take_capture_result(&mut self) -> Option<Result<PathBuf, ViewError>> {
+        self.capture_result.take()
+    }
+
+    pub(crate) fn render(
+        &mut self,
+        encoder: &mut GfxEncoder,
+        factory: &mut gfx_device_gl::Factory,
+        projection: Matrix4,
+    ) -> Result<(), ViewError> {
+        let view = self.camera.orthogonal();
+        self.sound_source_viewer
+            .render(encoder, factory, view, projection);
+        let mut result = Ok(());
+        for viewer in &mut self.field_slice_viewers {
+            result = result.and(viewer.render(encoder, factory, view, projection));
+        }
+        self.render_reflectors(encoder, factory, view, projection);
+        result
+    }
+
+    /// Draws the reflector planes once for the whole scene, after the slices.
+    pub(crate) fn render_reflectors(
+        &mut self,
+        encoder: &mut GfxEncoder,
+        factory: &mut gfx_device_gl::Factory,
+        view: Matrix4,
+        projection: Matrix4,
+    ) {
+        if let Some(planes) = &mut self.reflector_planes {
+            let settings = self.settings.borrow();
+            if self.reflectors_updated {
+                planes.update(factory, &settings.reflectors);
+                self.reflectors_updated = false;
+            }
+            if !settings.reflectors.is_empty() {
+                planes.render(encoder, view, projection);
+            }
+        }
+    }
+
+    pub(crate) fn set_render_target(
+        &mut self,
+        out_color: RenderTargetView<Resources, format::Srgba8>,
+        out_depth: DepthStencilView<Resources, format::DepthStencil>,
+    ) {
+        self.sound_source_viewer
+            .set_render_target(out_color.clone(), out_depth.clone());
+        for viewer in &mut self.field_slice_viewers {
+            viewer.set_render_target(out_color.clone(), out_depth.clone());
+        }
+        if let Some(planes) = &mut self.reflector_planes {
+            planes.set_render_target(out_color.clone(), out_depth.clone());
+        }
+        self.out_color = Some(out_color);
+        self.out_depth = Some(out_depth);
+    }
 }
 
 pub struct ViewWindow<F>
 where
-    F: FnMut(&mut UpdateHandler, Option<Button>) -> (),
+    F: FnMut(&mut UpdateHandler, Option<Button>),
 {
     pub update: Option<F>,
     update_handler: UpdateHandler,
@@ -85,14 +361,14 @@ where
 
 impl<F> ViewWindow<F>
 where
-    F: FnMut(&mut UpdateHandler, Option<Button>) -> (),
+    F: FnMut(&mut UpdateHandler, Option<Button>),
 {
     pub fn new(
         sources: Vec<SoundSource>,
         sound_source_viewer: SoundSourceViewer,
         field_slice_viewer: AcousticFiledSliceViewer,
         settings: ViewerSettings,
-    ) -> ViewWindow<F> {
+    ) -> Result<ViewWindow<F>, ViewError> {
         let opengl = OpenGL::V3_2;
         let mut window: PistonWindow = WindowSettings::new("", [640, 480])
             .exit_on_esc(true)
@@ -103,38 +379,42 @@ where
         window.set_ups(60);
         window.set_max_fps(1000);
         let projection = ViewWindow::<F>::get_projection(&window);
-        let first_person =
-            FirstPerson::new([90., -250.0, 120.0], FirstPersonSettings::keyboard_wasd());
-        let mut camera = first_person.camera(0.);
-        camera.set_yaw_pitch(0., -std::f32::consts::PI / 2.0);
-
-        let mut sound_source_viewer = sound_source_viewer;
-        let mut field_slice_viewer = field_slice_viewer;
-
-        let ref_sources = Rc::new(RefCell::new(sources));
-        sound_source_viewer.sources = Rc::downgrade(&ref_sources);
-        field_slice_viewer.sources = Rc::downgrade(&ref_sources);
-
-        let ref_settings = Rc::new(RefCell::new(settings));
-        sound_source_viewer.settings = Rc::downgrade(&ref_settings);
-        field_slice_viewer.settings = Rc::downgrade(&ref_settings);
 
-        sound_source_viewer.init_model();
-        field_slice_viewer.render_setting(&window, opengl);
-        sound_source_viewer.render_setting(&window, opengl);
+        let mut update_handler = UpdateHandler::new(
+            sources,
+            sound_source_viewer,
+            field_slice_viewer,
+            settings,
+            UpdateHandler::default_camera(),
+        );
+        update_handler
+            .sound_source_viewer
+            .render_setting(&window, opengl);
+        update_handler
+            .set_render_target(window.output_color.clone(), window.output_stencil.clone());
+        update_handler.initialize_viewers(&mut window.factory)?;
 
-        ViewWindow {
+        Ok(ViewWindow {
             update: None,
-            update_handler: UpdateHandler::new(
-                ref_sources,
-                sound_source_viewer,
-                field_slice_viewer,
-                ref_settings,
-                camera,
-            ),
+            update_handler,
             projection,
             window,
-        }
+        })
+    }
+
+    /// Same as `new`, but the sources are given as devices that can be moved as a unit
+    /// through `UpdateHandler::set_device_pose` and friends.
+    pub fn with_devices(
+        devices: Vec<Device>,
+        sound_source_viewer: SoundSourceViewer,
+        field_slice_viewer: AcousticFiledSliceViewer,
+        settings: ViewerSettings,
+    ) -> Result<ViewWindow<F>, ViewError> {
+        let sources = devices.iter().flat_map(|d| d.sources()).collect();
+        let mut window =
+            ViewWindow::new(sources, sound_source_viewer, field_slice_viewer, settings)?;
+        window.update_handler.set_devices(devices);
+        Ok(window)
     }
 
     pub fn start(self) {
@@ -147,6 +427,15 @@ where
                 update_fn(&mut update_handler, e.press_args());
                 update_handler.update_sources();
             }
+            if let Err(err) = update_handler.initialize_viewers(&mut window.factory) {
+                eprintln!("Failed to add a slice: {}", err);
+            }
+
+            if let Some(args) = e.update_args() {
+                if update_handler.settings.borrow().animate {
+                    update_handler.advance_time(args.dt);
+                }
+            }
 
             window.draw_3d(&e, |window| {
                 window
@@ -159,27 +448,105 @@ where
                     update_handler.camera.orthogonal(),
                     projection,
                 );
-                update_handler.field_slice_viewer.renderer(
-                    window,
-                    &e,
-                    update_handler.camera.orthogonal(),
+                for viewer in &mut update_handler.field_slice_viewers {
+                    let view = update_handler.camera.orthogonal();
+                    if let Err(err) = viewer.renderer(window, &e, view, projection) {
+                        eprintln!("Failed to update a slice: {}", err);
+                    }
+                }
+                let view = update_handler.camera.orthogonal();
+                update_handler.render_reflectors(
+                    &mut window.encoder,
+                    &mut window.factory,
+                    view,
                     projection,
                 );
             });
+            if e.render_args().is_some() {
+                if let Some(scale) = update_handler.capture.take() {
+                    let result = ViewWindow::<F>::capture(&mut window, &mut update_handler, scale);
+                    update_handler.capture_result = Some(result);
+                }
+            }
             if e.resize_args().is_some() {
                 projection = ViewWindow::<F>::get_projection(&window);
+                update_handler
+                    .set_render_target(window.output_color.clone(), window.output_stencil.clone());
             }
         }
     }
 
+    fn capture(
+        window: &mut PistonWindow,
+        update_handler: &mut UpdateHandler,
+        scale: u16,
+    ) -> Result<PathBuf, ViewError> {
+        let draw_size = window.window.draw_size();
+        let (draw_width, draw_height) = (draw_size.width as u32, draw_size.height as u32);
+        if draw_width == 0 || draw_height == 0 {
+            return Err(ViewError::CaptureEmpty {
+                width: draw_width,
+                height: draw_height,
+            });
+        }
+        let max = window
+            .factory
+            .get_capabilities()
+            .max_texture_size
+            .min(u16::MAX as usize);
+        let scaled = |len: u32| {
+            (len as usize)
+                .checked_mul(scale as usize)
+                .filter(|&len| len <= max)
+        };
+        let (width, height) = match (scaled(draw_width), scaled(draw_height)) {
+            (Some(width), Some(height)) => (width as u16, height as u16),
+            _ => {
+                return Err(ViewError::CaptureTooLarge {
+                    width: draw_width,
+                    height: draw_height,
+                    scale,
+                    max,
+                })
+            }
+        };
+        let target = RenderTarget::new(&mut window.factory, width, height)?;
+
+        window.window.make_current();
+        update_handler.set_render_target(target.out_color.clone(), target.out_depth.clone());
+        target.clear(&mut window.encoder);
+        let rendered = update_handler.render(
+            &mut window.encoder,
+            &mut window.factory,
+            target.projection(),
+        );
+        let image = target.read(&mut window.encoder, &mut window.device, &mut window.factory);
+        update_handler
+            .set_render_target(window.output_color.clone(), window.output_stencil.clone());
+        rendered?;
+        let image = downsample(&image?, scale as u32);
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis();
+        let path = PathBuf::from(format!("capture_{}.png", timestamp));
+        image.save(&path)?;
+        Ok(path)
+    }
+
     fn get_projection(w: &PistonWindow) -> Matrix4 {
         let draw_size = w.window.draw_size();
-        CameraPerspective {
-            fov: 60.0,
-            near_clip: 0.1,
-            far_clip: 1000.0,
-            aspect_ratio: (draw_size.width as f32) / (draw_size.height as f32),
-        }
-        .projection()
+        perspective(draw_size.width as f32, draw_size.height as f32)
+    }
+}
+
+pub(crate) fn perspective(width: f32, height: f32) -> Matrix4 {
+    CameraPerspective {
+        fov: 60.0,
+        near_clip: 0.1,
+        far_clip: 1000.0,
+        aspect_ratio: width / height,
     }
+    .projection()
 }